@@ -0,0 +1,106 @@
+use std::{collections::BTreeMap, error::Error, fs, io, path::Path};
+
+use toml::{Table, Value};
+
+use crate::solution::Part;
+
+pub const DEFAULT_ANSWERS_PATH: &str = "answers.toml";
+
+/// Known-correct answers, stored on disk as one `[dayN]` table per day with `part1`/`part2` keys.
+#[derive(Debug, Default)]
+pub struct Answers {
+    known: BTreeMap<(u8, u8), String>,
+}
+
+impl Answers {
+    /// Loads answers from the given path, treating a missing file as an empty store.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut known = BTreeMap::new();
+        for (day_key, parts) in contents.parse::<Table>()? {
+            let day: u8 = day_key
+                .strip_prefix("day")
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| format!("Expected a table named 'dayN', got '{}'", day_key))?;
+
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("Expected '{}' to be a table", day_key))?;
+
+            for part in Part::BOTH {
+                if let Some(value) = parts.get(&format!("part{}", part.number())) {
+                    known.insert((day, part.number()), value_to_answer(value));
+                }
+            }
+        }
+        Ok(Self { known })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut table = Table::new();
+        for ((day, part), answer) in &self.known {
+            let entry = table
+                .entry(format!("day{}", day))
+                .or_insert_with(|| Value::Table(Table::new()));
+
+            if let Value::Table(parts) = entry {
+                parts.insert(format!("part{}", part), Value::String(answer.clone()));
+            }
+        }
+        fs::write(path, table.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.known.get(&(day, part.number())).map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, answer: String) {
+        self.known.insert((day, part.number()), answer);
+    }
+}
+
+fn value_to_answer(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn saves_and_loads_answers() {
+        let path = env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+        let mut answers = Answers::default();
+        answers.record(1, Part::One, String::from("142"));
+        answers.record(1, Part::Two, String::from("281"));
+        answers.record(12, Part::Two, String::from("525152"));
+
+        answers.save(&path).unwrap();
+        let loaded = Answers::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.known, answers.known);
+    }
+
+    #[test]
+    fn loads_numeric_answers_and_missing_files() {
+        let path = env::temp_dir().join(format!("aoc-answers-numeric-{}.toml", std::process::id()));
+        fs::write(&path, "[day6]\npart1 = 288\n").unwrap();
+        let loaded = Answers::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get(6, Part::One), Some("288"));
+        assert_eq!(loaded.get(6, Part::Two), None);
+        assert!(Answers::load(&path).unwrap().known.is_empty());
+    }
+}
+
@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use super::vocabulary::Vocabulary;
+use crate::{error::Result, solution::Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+	const DAY: u8 = 1;
+
+	type Input = Vec<String>;
+
+	fn parse(input: &str) -> Result<Self::Input> {
+		Ok(input.lines().map(String::from).collect())
+	}
+
+	fn part1(lines: &Self::Input) -> Result<impl Display> {
+		Ok(calibration_sum(lines, &Vocabulary::digits()))
+	}
+
+	fn part2(lines: &Self::Input) -> Result<impl Display> {
+		Ok(calibration_sum(lines, &Vocabulary::english()))
+	}
+}
+
+/// Sums the calibration value of every line, reading digits with the given vocabulary. Lines that
+/// contain no digits count as 0.
+pub fn calibration_sum(lines: &[String], vocabulary: &Vocabulary) -> u32 {
+	lines
+		.iter()
+		.filter_map(|line| vocabulary.calibration_value(line))
+		.sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solution::{DynSolution, Part};
+
+	const EXAMPLE_PART1: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+	const EXAMPLE_PART2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+	#[test]
+	fn part1_example() {
+		assert_eq!(Day1.run(EXAMPLE_PART1, Part::One).unwrap().answer, "142");
+	}
+
+	#[test]
+	fn part2_example() {
+		assert_eq!(Day1.run(EXAMPLE_PART2, Part::Two).unwrap().answer, "281");
+	}
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use super::vocabulary::{TokenMatch, Vocabulary};
+
+/// How a single line of a calibration document was read.
+#[derive(Debug)]
+pub struct LineExplanation<'a> {
+	/// 1-based, to match the input file.
+	pub line_number: usize,
+	pub line: &'a str,
+	pub matches: Vec<TokenMatch<'a>>,
+	pub first: Option<TokenMatch<'a>>,
+	pub last: Option<TokenMatch<'a>>,
+}
+
+impl LineExplanation<'_> {
+	pub fn value(&self) -> Option<u32> {
+		Some(self.first?.digit * 10 + self.last?.digit)
+	}
+}
+
+/// Explains every line, picking the first and last digits with the same `Vocabulary` methods that
+/// `Vocabulary::calibration_value` uses.
+pub fn explain<'a>(
+	lines: impl IntoIterator<Item = &'a str>,
+	vocabulary: &'a Vocabulary,
+) -> Vec<LineExplanation<'a>> {
+	lines
+		.into_iter()
+		.enumerate()
+		.map(|(i, line)| {
+			LineExplanation {
+				line_number: i + 1,
+				line,
+				matches: vocabulary.matches(line),
+				first: vocabulary.first_match(line),
+				last: vocabulary.last_match(line),
+			}
+		})
+		.collect()
+}
+
+impl fmt::Display for LineExplanation<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "line {}: {:?} ", self.line_number, self.line)?;
+
+		let (Some(first), Some(last), Some(value)) = (self.first, self.last, self.value()) else {
+			return write!(f, "-> NO DIGITS");
+		};
+
+		writeln!(f, "-> {}", value)?;
+		let matches = self
+			.matches
+			.iter()
+			.map(|found| format!("{}@{}={}", found.token, found.offset, found.digit))
+			.collect::<Vec<_>>();
+		writeln!(f, "  matches: {}", matches.join(", "))?;
+		write!(
+			f,
+			"  first: {}@{}, last: {}@{}",
+			first.token, first.offset, last.token, last.offset
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn explains_overlapping_words() {
+		let english = Vocabulary::english();
+		let explanations = explain(["xtwone3four"], &english);
+		let line = &explanations[0];
+
+		let matches = line
+			.matches
+			.iter()
+			.map(|found| (found.token, found.offset))
+			.collect::<Vec<_>>();
+		assert_eq!(matches, [("two", 1), ("one", 3), ("3", 6), ("four", 7)]);
+		assert_eq!(line.first.unwrap().token, "two");
+		assert_eq!(line.last.unwrap().token, "four");
+		assert_eq!(line.value(), Some(24));
+		assert_eq!(
+			line.to_string(),
+			"line 1: \"xtwone3four\" -> 24\n  matches: two@1=2, one@3=1, 3@6=3, four@7=4\n  first: two@1, last: four@7"
+		);
+	}
+
+	#[test]
+	fn agrees_with_calibration_values() {
+		let custom = Vocabulary::digits().with("on", 7).with("one", 1).with("ne", 9);
+		let cases = [
+			(Vocabulary::roman(), ["viii", "xivx", "ix2vi", "iv", "xyz"]),
+			(custom, ["one", "xoneb", "ne2on", "onne", "none"]),
+		];
+
+		for (vocabulary, lines) in &cases {
+			for explanation in explain(*lines, vocabulary) {
+				let line = explanation.line;
+				assert_eq!(explanation.value(), vocabulary.calibration_value(line), "{:?}", line);
+			}
+		}
+	}
+
+	#[test]
+	fn flags_lines_without_digits() {
+		let digits = Vocabulary::digits();
+		let explanations = explain(["1abc2", "eightwo"], &digits);
+
+		assert_eq!(explanations[0].value(), Some(12));
+		assert_eq!(explanations[1].value(), None);
+		assert_eq!(explanations[1].to_string(), "line 2: \"eightwo\" -> NO DIGITS");
+	}
+}
@@ -1,2 +1,4 @@
-pub mod part1;
-pub mod part2;
+pub mod day1;
+pub mod explain;
+pub mod vocabulary;
+pub use day1::Day1;
@@ -0,0 +1,200 @@
+use std::{cmp::Reverse, error::Error, fs, path::Path};
+
+use crate::{
+	error::{unexpected, Unexpected},
+	util::parse_num,
+};
+
+/// The tokens that count as digits in a calibration document, each mapped to the digit it stands
+/// for. The first and last digits of a line are found by searching from each end separately, so
+/// tokens that share letters (`eightwo`) are found from both sides without overlapping matches.
+/// Where several tokens match at the same place, the longest wins, so `viii` reads as 8 rather
+/// than 5.
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+	tokens: Vec<(String, u32)>,
+}
+
+const ENGLISH_WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+const ROMAN_NUMERALS: [&str; 9] = ["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"];
+
+impl Vocabulary {
+	/// The literal digits `0`-`9`, as in part 1.
+	pub fn digits() -> Self {
+		(0..=9).fold(Self::default(), |vocabulary, digit| vocabulary.with(&digit.to_string(), digit))
+	}
+
+	/// Literal digits plus the spelled-out digits `one` to `nine`, as in part 2.
+	pub fn english() -> Self {
+		Self::digits().with_words(ENGLISH_WORDS)
+	}
+
+	/// Literal digits plus the lowercase roman numerals `i` to `ix`.
+	pub fn roman() -> Self {
+		Self::digits().with_words(ROMAN_NUMERALS)
+	}
+
+	/// Adds (or redefines) a single token.
+	pub fn with(mut self, token: &str, digit: u32) -> Self {
+		self.tokens.retain(|(existing, _)| existing != token);
+		self.tokens.push((String::from(token), digit));
+		self.tokens.sort_by_key(|(token, _)| Reverse(token.len()));
+		self
+	}
+
+	/// Adds tokens for the digits 1 to 9, in order.
+	pub fn with_words<'w>(self, words: impl IntoIterator<Item = &'w str>) -> Self {
+		words
+			.into_iter()
+			.zip(1..)
+			.fold(self, |vocabulary, (word, digit)| vocabulary.with(word, digit))
+	}
+
+	/// Reads a vocabulary with one `[token] [digit]` pair per line. Blank lines and lines starting
+	/// with `#` are ignored. Literal digits are only included if listed.
+	pub fn parse(s: &str) -> Result<Self, Unexpected<'_>> {
+		s.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.try_fold(Self::default(), |vocabulary, line| {
+				let (token, digit) = line
+					.split_once(char::is_whitespace)
+					.ok_or_else(|| unexpected(line, "a line of the form [token] [digit]"))?;
+
+				let digit = digit.trim();
+				match parse_num(digit)? {
+					digit @ 0..=9 => Ok(vocabulary.with(token, digit)),
+					_ => Err(unexpected(digit, "a digit from 0 to 9")),
+				}
+			})
+	}
+
+	pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+		let contents = fs::read_to_string(path)?;
+		let vocabulary = Self::parse(&contents).map_err(|err| {
+			format!("{}: expected {}, found '{}'", path.display(), err.expected, err.found)
+		})?;
+		Ok(vocabulary)
+	}
+
+	pub fn first(&self, line: &str) -> Option<u32> {
+		self.first_match(line).map(|found| found.digit)
+	}
+
+	pub fn last(&self, line: &str) -> Option<u32> {
+		self.last_match(line).map(|found| found.digit)
+	}
+
+	/// The token starting earliest in the line, preferring the longest where several start at once.
+	pub fn first_match(&self, line: &str) -> Option<TokenMatch<'_>> {
+		line.char_indices().find_map(|(offset, _)| {
+			let (token, digit) = self.token_where(|token| line[offset..].starts_with(token))?;
+			Some(TokenMatch { offset, token, digit })
+		})
+	}
+
+	/// The token ending latest in the line, preferring the longest where several end at once.
+	pub fn last_match(&self, line: &str) -> Option<TokenMatch<'_>> {
+		line.char_indices().rev().find_map(|(i, c)| {
+			let end = i + c.len_utf8();
+			let (token, digit) = self.token_where(|token| line[..end].ends_with(token))?;
+			Some(TokenMatch {
+				offset: end - token.len(),
+				token,
+				digit,
+			})
+		})
+	}
+
+	/// The two-digit number formed by the first and last digits, if the line contains any.
+	pub fn calibration_value(&self, line: &str) -> Option<u32> {
+		Some(self.first(line)? * 10 + self.last(line)?)
+	}
+
+	/// Every occurrence of every token in the line, ordered by offset and then longest first.
+	/// Occurrences may overlap.
+	pub fn matches(&self, line: &str) -> Vec<TokenMatch<'_>> {
+		line.char_indices()
+			.flat_map(|(offset, _)| {
+				self.tokens
+					.iter()
+					.filter(move |(token, _)| line[offset..].starts_with(token.as_str()))
+					.map(move |(token, digit)| TokenMatch {
+						offset,
+						token,
+						digit: *digit,
+					})
+			})
+			.collect()
+	}
+
+	fn token_where(&self, matches: impl Fn(&str) -> bool) -> Option<(&str, u32)> {
+		self.tokens
+			.iter()
+			.find(|(token, _)| matches(token))
+			.map(|(token, digit)| (token.as_str(), *digit))
+	}
+}
+
+/// A token found in a line, at a byte offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenMatch<'a> {
+	pub offset: usize,
+	pub token: &'a str,
+	pub digit: u32,
+}
+
+impl TokenMatch<'_> {
+	pub fn end(&self) -> usize {
+		self.offset + self.token.len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn words_sharing_letters_match_from_both_ends() {
+		let english = Vocabulary::english();
+		assert_eq!(english.calibration_value("eightwo"), Some(82));
+		assert_eq!(english.calibration_value("zoneight"), Some(18));
+		assert_eq!(english.calibration_value("twone3oneight"), Some(28));
+	}
+
+	#[test]
+	fn lines_without_digits_have_no_value() {
+		assert_eq!(Vocabulary::digits().calibration_value("eightwo"), None);
+		assert_eq!(Vocabulary::english().calibration_value("abc"), None);
+	}
+
+	#[test]
+	fn single_digit_is_both_first_and_last() {
+		assert_eq!(Vocabulary::digits().calibration_value("treb7uchet"), Some(77));
+	}
+
+	#[test]
+	fn zero_can_be_added() {
+		let vocabulary = Vocabulary::english().with("zero", 0);
+		assert_eq!(vocabulary.calibration_value("zerone"), Some(1));
+		assert_eq!(vocabulary.calibration_value("sevenzero"), Some(70));
+	}
+
+	#[test]
+	fn longest_roman_numeral_wins() {
+		let roman = Vocabulary::roman();
+		assert_eq!(roman.calibration_value("viii"), Some(88));
+		assert_eq!(roman.calibration_value("xivx"), Some(44));
+		assert_eq!(roman.calibration_value("ix2vi"), Some(96));
+	}
+
+	#[test]
+	fn parses_custom_tokens() {
+		let french = Vocabulary::parse("# French\nun 1\ndeux 2\n\ntrois 3\nhuit 8\n").unwrap();
+		assert_eq!(french.calibration_value("huitroisdeux"), Some(82));
+		assert_eq!(french.calibration_value("2"), None);
+
+		let err = Vocabulary::parse("dix 10").unwrap_err();
+		assert_eq!(err.found, "10");
+	}
+}
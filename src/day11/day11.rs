@@ -1,36 +1,40 @@
 use itertools::*;
-use std::io::{BufReader, Read};
+use std::fmt::Display;
+
+use crate::{
+    error::{unexpected, Result, Unexpected},
+    solution::Solution,
+};
 
 #[derive(PartialEq, Eq, Clone, Hash, Debug)]
-struct Point {
+pub struct Point {
     pub x: u128,
     pub y: u128,
 }
 
-struct GalaxyMap<const N: usize> {
+pub struct GalaxyMap {
     pub galaxies: Vec<Point>,
     pub x_vacant: Vec<bool>,
     pub y_vacant: Vec<bool>,
 }
 
-impl<const N: usize> GalaxyMap<N> {
-    pub fn from(reader: &mut BufReader<Box<dyn Read>>) -> Self {
-        let mut buffer = String::new();
-        reader
-            .read_to_string(&mut buffer)
-            .expect("Failed to read input to string");
-
-        let n_cols = buffer
-            .find('\n')
-            .expect("Fatal - failed to find newline in supposedly multi-line input");
-        let n_rows = buffer.len() / n_cols;
+impl GalaxyMap {
+    pub fn from(buffer: &str) -> Result<Self, Unexpected<'_>> {
+        let n_cols = buffer.lines().next().map_or(0, str::len);
+        let n_rows = buffer.lines().count();
 
         let mut galaxies = vec![];
         let mut x_vacant = vec![true; n_cols];
         let mut y_vacant = vec![true; n_rows];
 
         for (y, line) in buffer.lines().enumerate() {
-            for (x, char) in line.chars().enumerate() {
+            if line.len() != n_cols {
+                return Err(unexpected(line, format!("a row of {} cells", n_cols)));
+            }
+            for (x, char) in line.char_indices() {
+                if char != '#' && char != '.' {
+                    return Err(unexpected(&line[x..x + char.len_utf8()], "'.' or '#'"));
+                }
                 if char == '#' {
                     x_vacant[x] = false;
                     y_vacant[y] = false;
@@ -41,16 +45,16 @@ impl<const N: usize> GalaxyMap<N> {
                 }
             }
         }
-        Self {
+        Ok(Self {
             galaxies,
             x_vacant,
             y_vacant,
-        }
+        })
     }
 
-    pub fn expand(&self, a: &Point, b: &Point) -> Point {
-        let x_expansion = (a.x..b.x).filter(|x| self.x_vacant[*x as usize]).count() * (N - 1);
-        let y_expansion = (a.y..b.y).filter(|y| self.y_vacant[*y as usize]).count() * (N - 1);
+    pub fn expand(&self, a: &Point, b: &Point, factor: usize) -> Point {
+        let x_expansion = (a.x..b.x).filter(|x| self.x_vacant[*x as usize]).count() * (factor - 1);
+        let y_expansion = (a.y..b.y).filter(|y| self.y_vacant[*y as usize]).count() * (factor - 1);
 
         Point {
             x: b.x + x_expansion as u128,
@@ -63,23 +67,67 @@ impl<const N: usize> GalaxyMap<N> {
     }
 }
 
-pub fn part1(reader: &mut BufReader<Box<dyn Read>>) -> u128 {
-    let map = GalaxyMap::<2>::from(reader);
-    solve(map)
-}
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = GalaxyMap;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        GalaxyMap::from(input).map_err(|err| err.locate(Self::DAY, input))
+    }
 
-pub fn part2(reader: &mut BufReader<Box<dyn Read>>) -> u128 {
-    let map = GalaxyMap::<1000000>::from(reader);
-    solve(map)
+    fn part1(map: &Self::Input) -> Result<impl Display> {
+        Ok(solve(map, 2))
+    }
+
+    fn part2(map: &Self::Input) -> Result<impl Display> {
+        Ok(solve(map, 1000000))
+    }
 }
 
-fn solve<const N: usize>(map: GalaxyMap<N>) -> u128 {
+fn solve(map: &GalaxyMap, expansion_factor: usize) -> u128 {
     map.all_galaxy_pairs()
         .iter()
-        .map(|(p0, p1)| manhattan_dist(&map.expand(p1, p0), &map.expand(p0, p1)))
+        .map(|(p0, p1)| {
+            manhattan_dist(
+                &map.expand(p1, p0, expansion_factor),
+                &map.expand(p0, p1, expansion_factor),
+            )
+        })
         .sum()
 }
 
 fn manhattan_dist(p0: &Point, p1: &Point) -> u128 {
     p1.x.abs_diff(p0.x) + p1.y.abs_diff(p0.y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day11.run(EXAMPLE, Part::One).unwrap().answer, "374");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day11.run(EXAMPLE, Part::Two).unwrap().answer, "82000210");
+    }
+}
@@ -1,2 +1,2 @@
 pub mod day11;
-pub use day11::{part1, part2};
+pub use day11::Day11;
@@ -1,28 +1,44 @@
 use michie::memoized;
 use std::collections::HashMap;
-use std::str::FromStr;
+use std::fmt::Display;
+
+use crate::error::{unexpected, Result, Unexpected};
+use crate::solution::Solution;
+use crate::util::parse_num;
 
 const OPERATIONAL: char = '.';
 const BROKEN: char = '#';
 const UNKNOWN: char = '?';
 
-pub fn part1(lines: impl Iterator<Item = String>) -> u64 {
-    lines
-        .map(|line| num_valid_arrangements(line.parse().expect("Failed to parse input line")))
-        .sum()
-}
-pub fn part2(lines: impl Iterator<Item = String>) -> u64 {
-    let folding_factor = 5;
-
-    lines
-        .map(|line| {
-            num_valid_arrangements(
-                unfold_record(line, folding_factor)
-                    .parse()
-                    .expect("Failed to parse input line"),
-            )
-        })
-        .sum()
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Vec<SpringState>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| SpringState::from(line).map_err(|err| err.locate(Self::DAY, input)))
+            .collect()
+    }
+
+    fn part1(records: &Self::Input) -> Result<impl Display> {
+        Ok(records
+            .iter()
+            .map(|record| num_valid_arrangements(record.clone()))
+            .sum::<u64>())
+    }
+
+    fn part2(records: &Self::Input) -> Result<impl Display> {
+        let folding_factor = 5;
+
+        Ok(records
+            .iter()
+            .map(|record| num_valid_arrangements(record.unfold(folding_factor)))
+            .sum::<u64>())
+    }
 }
 
 #[memoized(key_expr = state.clone(), store_type = HashMap<SpringState, u64>)]
@@ -39,13 +55,9 @@ fn num_valid_arrangements(state: SpringState) -> u64 {
     }
 
     let num_arrangements_if_broken = match state.current_spring() {
-        BROKEN | UNKNOWN => {
-            if state.can_match_run() {
-                let next_state = state.consume_run();
-                num_valid_arrangements(next_state)
-            } else {
-                0
-            }
+        BROKEN | UNKNOWN if state.can_match_run() => {
+            let next_state = state.consume_run();
+            num_valid_arrangements(next_state)
         }
         _ => 0,
     };
@@ -63,39 +75,8 @@ fn num_valid_arrangements(state: SpringState) -> u64 {
     num_arrangements_if_broken + num_arrangements_if_operational
 }
 
-fn unfold_record(record: String, folding_factor: usize) -> String {
-    record
-        .split_once(' ')
-        .map(|(spring_section, run_length_section)| {
-            format!(
-                "{} {}",
-                repeat_and_intersperse(spring_section, '?', folding_factor),
-                repeat_and_intersperse(run_length_section, ',', folding_factor)
-            )
-        })
-        .expect(&format!("Failed to unfold line '{}'", record))
-}
-
-fn repeat_and_intersperse(s: &str, sep: char, n: usize) -> String {
-    let original_length = s.len();
-
-    s.repeat(n)
-        .chars()
-        .enumerate()
-        .flat_map(|(i, c)| {
-            if i > 0 && i % original_length == 0 {
-                Some(sep)
-            } else {
-                None
-            }
-            .into_iter()
-            .chain(std::iter::once(c))
-        })
-        .collect::<String>()
-}
-
 #[derive(Hash, PartialEq, Eq, Clone)]
-struct SpringState {
+pub struct SpringState {
     pub springs: Vec<char>,
     pub run_lengths: Vec<usize>,
 }
@@ -155,18 +136,26 @@ impl SpringState {
     }
 }
 
-impl FromStr for SpringState {
-    type Err = Box<dyn std::error::Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl SpringState {
+    pub fn from(s: &str) -> Result<Self, Unexpected<'_>> {
         let (spring_section, run_length_section) = s
             .split_once(' ')
-            .expect("Input lines should be of format '[springs] [counts]");
+            .ok_or_else(|| unexpected(s, "a line of the form '[springs] [counts]'"))?;
+
+        if let Some((i, c)) = spring_section
+            .char_indices()
+            .find(|(_, c)| ![OPERATIONAL, BROKEN, UNKNOWN].contains(c))
+        {
+            return Err(unexpected(
+                &spring_section[i..i + c.len_utf8()],
+                "a spring ('.', '#' or '?')",
+            ));
+        }
 
         let run_lengths: Vec<usize> = run_length_section
             .split(',')
-            .flat_map(|c| c.parse())
-            .collect();
+            .map(parse_num)
+            .collect::<Result<_, _>>()?;
 
         let mut springs: Vec<char> = spring_section.chars().collect();
         springs.push(OPERATIONAL);
@@ -176,4 +165,49 @@ impl FromStr for SpringState {
             run_lengths,
         })
     }
+
+    /// Repeats the record `folding_factor` times, joining the springs with unknowns.
+    pub fn unfold(&self, folding_factor: usize) -> Self {
+        // Leave off the trailing operational spring added when parsing, and add it back at the end.
+        let record = &self.springs[..self.springs.len() - 1];
+        let mut springs = vec![];
+
+        for i in 0..folding_factor {
+            if i > 0 {
+                springs.push(UNKNOWN);
+            }
+            springs.extend_from_slice(record);
+        }
+        springs.push(OPERATIONAL);
+
+        Self {
+            springs,
+            run_lengths: self.run_lengths.repeat(folding_factor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day12.run(EXAMPLE, Part::One).unwrap().answer, "21");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day12.run(EXAMPLE, Part::Two).unwrap().answer, "525152");
+    }
 }
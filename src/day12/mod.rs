@@ -1,2 +1,2 @@
 pub mod day12;
-pub use day12::{part1, part2};
+pub use day12::Day12;
@@ -1,4 +1,9 @@
-use std::io::{BufReader, Read};
+use std::fmt::Display;
+
+use crate::{
+	error::{unexpected, Result, Unexpected},
+	solution::Solution,
+};
 
 const HORIZONTAL_MULTIPLIER: usize = 100;
 
@@ -10,17 +15,15 @@ pub struct MySpecialIterator<'a> {
 
 pub struct MySpecialIteratorMirrored<'a> {
 	items: &'a Vec<&'a str>,
-	mirror_pos: usize,
 	left: Option<usize>,
 	right: Option<usize>,
 }
 
 
 impl MySpecialIterator<'_> {
-	pub fn mirrored_around(&self, pos: usize) -> MySpecialIteratorMirrored {
+	pub fn mirrored_around(&self, pos: usize) -> MySpecialIteratorMirrored<'_> {
 		MySpecialIteratorMirrored {
 			items: self.lines,
-			mirror_pos: pos,
 			left: pos.checked_sub(1),
 			right: Some(pos),
 		}
@@ -31,11 +34,11 @@ impl<'a> Iterator for MySpecialIterator<'a> {
 	type Item = (&'a str, &'a str);
 
 	fn next(&mut self) -> Option<Self::Item> {
-		if self.pos >= self.lines.len() - 1 {
+		if self.pos >= self.lines.len().saturating_sub(1) {
 			None
 		} else {
 			let next = Some((self.lines[self.pos], self.lines[self.pos + 1]));
-			self.pos = self.pos + 1;
+			self.pos += 1;
 			next
 		}
 	}
@@ -83,30 +86,63 @@ impl MySpecialIteratorMirrored<'_> {
 	}
 }
 
-pub fn part1(reader: &mut BufReader<Box<dyn Read>>) -> usize {
-	solve(reader, &|left, right| hamming_distance(left, right) == 0)
+pub struct Day13;
+
+impl Solution for Day13 {
+	const DAY: u8 = 13;
+
+	type Input = Vec<String>;
+
+	fn parse(input: &str) -> Result<Self::Input> {
+		input
+			.trim_end()
+			.split("\n\n")
+			.map(|mirror| {
+				check_mirror(mirror)
+					.map(|_| String::from(mirror))
+					.map_err(|err| err.locate(Self::DAY, input))
+			})
+			.collect()
+	}
+
+	fn part1(mirrors: &Self::Input) -> Result<impl Display> {
+		Ok(solve(mirrors, 0))
+	}
+
+	fn part2(mirrors: &Self::Input) -> Result<impl Display> {
+		Ok(solve(mirrors, 1))
+	}
 }
 
-pub fn part2(reader: &mut BufReader<Box<dyn Read>>) -> usize {
-	solve(reader, &|left, right| hamming_distance(left, right) == 1)
+/// Checks that a mirror is a rectangle of '.' and '#', which the rest of the solution assumes.
+fn check_mirror(mirror: &str) -> Result<(), Unexpected<'_>> {
+	let first_line = mirror.lines().next().unwrap_or(mirror);
+	let width = first_line.len();
+	if width == 0 {
+		return Err(unexpected(first_line, "a row of '.' and '#' cells"));
+	}
+
+	for line in mirror.lines() {
+		if line.len() != width {
+			return Err(unexpected(line, format!("a row of {} cells", width)));
+		}
+		if let Some((i, c)) = line.char_indices().find(|(_, c)| *c != '.' && *c != '#') {
+			return Err(unexpected(&line[i..i + c.len_utf8()], "'.' or '#'"));
+		}
+	}
+	Ok(())
 }
 
-pub fn solve(
-	reader: &mut BufReader<Box<dyn Read>>,
-	predicate: &impl Fn(&str, &str) -> bool,
-) -> usize {
-	let mut buffer = String::new();
-	reader
-		.read_to_string(&mut buffer)
-		.expect("Failed to read input");
-
-	buffer
-		.split("\n\n")
-		.map(|mirror| solve_single_mirror(mirror, predicate))
+/// Sums the reflection scores of each mirror, where a valid reflection line is one whose
+/// mirrored rows differ in exactly `smudges` cells in total.
+pub fn solve(mirrors: &[String], smudges: u32) -> usize {
+	mirrors
+		.iter()
+		.map(|mirror| solve_single_mirror(mirror, smudges))
 		.sum()
 }
 
-fn solve_single_mirror(mirror: &str, predicate: &impl Fn(&str, &str) -> bool) -> usize {
+fn solve_single_mirror(mirror: &str, smudges: u32) -> usize {
 	let lines = mirror.lines().collect::<Vec<_>>();
 	let lines_rotated = rotate(&lines);
 	let rot_len = lines_rotated.len();
@@ -120,27 +156,25 @@ fn solve_single_mirror(mirror: &str, predicate: &impl Fn(&str, &str) -> bool) ->
 		pos: 0,
 	};
 
-	if let Some(horizontal) = find_mirror_line(&mut vertical_iterator, predicate) {
+	if let Some(horizontal) = find_mirror_line(&mut vertical_iterator, smudges) {
 		horizontal * HORIZONTAL_MULTIPLIER
-	} else if let Some(vertical) = find_mirror_line(&mut horizontal_iterator, predicate) {
+	} else if let Some(vertical) = find_mirror_line(&mut horizontal_iterator, smudges) {
 		rot_len - vertical
 	} else {
 		0
 	}
 }
 
-fn find_mirror_line(
-	iter: &mut MySpecialIterator,
-	predicate: &impl Fn(&str, &str) -> bool,
-) -> Option<usize> {
+fn find_mirror_line(iter: &mut MySpecialIterator, smudges: u32) -> Option<usize> {
 	let mut potential_mirror_lines = iter
 		.clone()
 		.enumerate()
-		.filter_map(|(i, (left, right))| predicate(left, right).then_some(i + 1));
+		.filter_map(|(i, (left, right))| (hamming_distance(left, right) <= smudges).then_some(i + 1));
 
 	potential_mirror_lines.find(|pos| {
 		iter.mirrored_around(*pos)
-			.all(|(left, right)| predicate(left, right))
+			.map(|(left, right)| hamming_distance(left, right))
+			.sum::<u32>() == smudges
 	})
 }
 
@@ -167,7 +201,7 @@ fn to_bits(line: &str) -> i32 {
 		.map(|c| match c {
 			'.' => 0,
 			'#' => 1,
-			_ => panic!("Encountered invalid char '{}' in input", c),
+			_ => unreachable!("Encountered invalid char '{}' in input", c),
 		})
 		.fold(0, |acc, n| (acc << 1) | n)
 }
@@ -175,3 +209,51 @@ fn to_bits(line: &str) -> i32 {
 fn hamming_distance(a: &str, b: &str) -> u32 {
 	(to_bits(a) ^ to_bits(b)).count_ones()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solution::{DynSolution, Part};
+
+	const EXAMPLE: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.##..##.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+	#[test]
+	fn part1_example() {
+		assert_eq!(Day13.run(EXAMPLE, Part::One).unwrap().answer, "405");
+	}
+
+	#[test]
+	fn part2_example() {
+		assert_eq!(Day13.run(EXAMPLE, Part::Two).unwrap().answer, "400");
+	}
+
+	#[test]
+	fn rejects_empty_patterns() {
+		let err = Day13::parse("#.\n.#\n\n\n\n#.\n.#\n").unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"day 13, line 4, column 1: expected a row of '.' and '#' cells, found end of line"
+		);
+	}
+
+	#[test]
+	fn allows_trailing_blank_lines() {
+		assert_eq!(Day13::parse("#.\n.#\n\n\n").unwrap().len(), 1);
+	}
+}
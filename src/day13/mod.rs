@@ -1,2 +1,2 @@
 pub mod day13;
-pub use day13::{part1, part2};
+pub use day13::Day13;
@@ -1 +1,28 @@
-pub fn part1(s: &str) {}
+use std::fmt::Display;
+
+use crate::{
+    error::Result,
+    solution::{Solution, Unsolved},
+};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    const FINISHED: bool = false;
+
+    type Input = ();
+
+    fn parse(_input: &str) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn part1(_input: &Self::Input) -> Result<impl Display> {
+        Ok(Unsolved)
+    }
+
+    fn part2(_input: &Self::Input) -> Result<impl Display> {
+        Ok(Unsolved)
+    }
+}
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
     Up,
@@ -9,7 +9,7 @@ enum Direction {
 }
 
 impl Direction {
-    fn inverse(&self) -> Self {
+    pub fn inverse(&self) -> Self {
         match self {
             Direction::Left => Direction::Right,
             Direction::Right => Direction::Left,
@@ -19,7 +19,7 @@ impl Direction {
     }
 }
 
-struct Grid<T: From<char>> {
+pub struct Grid<T: From<char>> {
     inner: Vec<T>,
     n_rows: usize,
     n_cols: usize,
@@ -28,10 +28,10 @@ struct Grid<T: From<char>> {
 impl<T: From<char>> Grid<T> {
     pub fn next(&self, i: usize, d: Direction) -> Option<(usize, &T)> {
         match d {
-            Direction::Left => (i % self.n_cols != 0).then_some(i - 1),
-            Direction::Right => ((i + 1) % self.n_cols != 0).then_some(i + 1),
-            Direction::Up => (i >= self.n_cols).then_some(i - self.n_cols),
-            Direction::Down => (i < self.n_rows - self.n_cols).then_some(i + self.n_cols),
+            Direction::Left => (!i.is_multiple_of(self.n_cols)).then(|| i - 1),
+            Direction::Right => (!(i + 1).is_multiple_of(self.n_cols)).then_some(i + 1),
+            Direction::Up => (i >= self.n_cols).then(|| i - self.n_cols),
+            Direction::Down => (i / self.n_cols + 1 < self.n_rows).then_some(i + self.n_cols),
         }
         .map(|i| (i, &self.inner[i]))
     }
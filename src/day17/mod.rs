@@ -1,3 +1,3 @@
 mod day17;
-mod grid;
-pub use day17::part1;
+pub mod grid;
+pub use day17::Day17;
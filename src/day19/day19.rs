@@ -1,30 +1,77 @@
-use std::io::{BufReader, Read};
-use crate::day19::{processor::PartProccessor, part::MachinePart, workflow::Workflow};
+use std::fmt::Display;
+use crate::day19::{part::MachinePart, workflow::Workflow};
+use crate::error::{end_of, Result};
+use crate::solution::{Solution, Unsolved};
 
-pub fn part1(reader: &mut BufReader<Box<dyn Read>>) -> u64 {
-	let mut buffer = String::new();
-	reader
-		.read_to_string(&mut buffer)
-		.expect("Failed to read input");
+pub struct Day19;
 
-	let (workflow_section, parts_section) = buffer
-		.split_once("\n\n")
-		.or_else(|| buffer.split_once("\r\n\r\n"))
-		.expect("Input should be in two parts, separated by a blank line");
+impl Solution for Day19 {
+	const DAY: u8 = 19;
 
-	let workflows: Vec<Workflow> = workflow_section
-		.lines()
-		.map(Workflow::from)
-		.collect();
+	const FINISHED: bool = false;
 
-	println!("{:?}", workflows.first());
+	type Input = (Vec<Workflow>, Vec<MachinePart>);
 
-	let parts: Vec<MachinePart> = parts_section
-		.lines()
-		.flat_map(MachinePart::from)
-		.collect();
+	fn parse(input: &str) -> Result<Self::Input> {
+		let (workflow_section, parts_section) = input
+			.split_once("\n\n")
+			.unwrap_or((input, end_of(input)));
 
-	let part_processor = PartProccessor::new(workflows);
+		let workflows = workflow_section
+			.lines()
+			.map(Workflow::from)
+			.collect::<Result<Vec<_>, _>>();
 
-	0
-}
\ No newline at end of file
+		let parts = parts_section
+			.lines()
+			.map(MachinePart::from)
+			.collect::<Result<Vec<_>, _>>();
+
+		workflows
+			.and_then(|workflows| Ok((workflows, parts?)))
+			.map_err(|err| err.locate(Self::DAY, input))
+	}
+
+	fn part1(_input: &Self::Input) -> Result<impl Display> {
+		Ok(Unsolved)
+	}
+
+	fn part2(_input: &Self::Input) -> Result<impl Display> {
+		Ok(Unsolved)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+	#[test]
+	fn parses_example() {
+		let (workflows, parts) = Day19::parse(EXAMPLE).unwrap();
+		assert_eq!(workflows.len(), 11);
+		assert_eq!(workflows[0].name, "px");
+		assert_eq!(parts.len(), 5);
+		assert_eq!(parts[0].x, 787);
+	}
+}
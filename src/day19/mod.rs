@@ -1,5 +1,8 @@
+// Still in progress: the workflow rules and part processor aren't wired up to a solution yet.
+#![allow(dead_code)]
+
 mod day19;
-mod part;
+pub mod part;
 mod processor;
 pub mod workflow;
-pub use day19::part1;
\ No newline at end of file
+pub use day19::Day19;
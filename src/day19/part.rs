@@ -1,6 +1,5 @@
-use std::error::Error;
-
-use crate::util::regex_or_panic;
+use crate::error::{unexpected, Unexpected};
+use crate::util::{parse_num, regex_or_panic};
 use regex::Regex;
 
 lazy_static! {
@@ -9,7 +8,7 @@ lazy_static! {
 
 const DIMENSIONS: &[&str; 4] = &["x", "m", "a", "s"];
 
- pub struct MachinePart {
+pub struct MachinePart {
 	pub x: i64,
 	pub m: i64,
 	pub a: i64,
@@ -20,29 +19,29 @@ const DIMENSIONS: &[&str; 4] = &["x", "m", "a", "s"];
 pub enum PartAttribute { X, M, A, S }
 
 impl MachinePart {
-	pub fn from(s: &str) -> Result<Self, Box<dyn Error>> {
+	pub fn from(s: &str) -> Result<Self, Unexpected<'_>> {
 		let (_, [x, m, a, s]) = PART_REGEX
 			.captures(s)
-			.expect(&format!("Part regex failed to match line '{}'", s))
+			.ok_or_else(|| unexpected(s, "a part of the form {x=N,m=N,a=N,s=N}"))?
 			.extract();
 
 		Ok(Self {
-			x: x.parse()?,
-			m: m.parse()?,
-			a: a.parse()?,
-			s: s.parse()?
+			x: parse_num(x)?,
+			m: parse_num(m)?,
+			a: parse_num(a)?,
+			s: parse_num(s)?
 		})
 	}
 }
 
 impl PartAttribute {
-	pub fn from(c: &char) -> Self {
-		match c {
-			'x' => Self::X,
-			'm' => Self::M,
-			'a' => Self::A,
-			's' => Self::S,
-			_ => panic!("Attempted to parse invalid char '{}' as PartAttribute", c)
+	pub fn from(s: &str) -> Result<Self, Unexpected<'_>> {
+		match s {
+			"x" => Ok(Self::X),
+			"m" => Ok(Self::M),
+			"a" => Ok(Self::A),
+			"s" => Ok(Self::S),
+			_ => Err(unexpected(s, "a part attribute (x, m, a or s)"))
 		}
 	}
 }
\ No newline at end of file
@@ -30,11 +30,11 @@ impl PartProccessor {
 
 	fn expand(&mut self, dimension: &PartAttribute) {
 		let d = *dimension as usize;
-		self.min_vals[d] = self.min_vals[d] + 1;
+		self.min_vals[d] += 1;
 	}
 
 	fn contract(&mut self, dimension: &PartAttribute) {
 		let d = *dimension as usize;
-		self.min_vals[d] = self.min_vals[d] - 1;
+		self.min_vals[d] -= 1;
 	}
 }
@@ -1,4 +1,5 @@
-use crate::util::regex_or_panic;
+use crate::error::{unexpected, Unexpected};
+use crate::util::{parse_num, regex_or_panic};
 use super::part::PartAttribute;
 use regex::Regex;
 
@@ -7,13 +8,13 @@ lazy_static! {
 	static ref RULE_REGEX: Regex = regex_or_panic(r"?:\w[<|>]\d+:)?\w+");
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Workflow {
 	pub name: String,
 	rules: Vec<Rule>
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Rule {
 	operand: Option<PartAttribute>,
 	comparator: Option<Comparator>,
@@ -21,59 +22,58 @@ struct Rule {
 	out: String
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Comparator { LT, GT }
 
 
 impl Workflow {
-	pub fn from(line: &str) -> Self {
+	pub fn from(line: &str) -> Result<Self, Unexpected<'_>> {
 		let sections = WORKFLOW_REGEX
 			.captures(line)
-			.expect(&format!("Fatal - input line did not match workflow regex: '{}'", line));
+			.ok_or_else(|| unexpected(line, "a workflow of the form name{rule,rule,...}"))?;
 
 		let name = String::from(&sections["name"]);
-		let rules = sections["rules"]
+		let rules = sections
+			.name("rules")
+			.map_or("", |rules| &line[rules.range()])
 			.split(",")
 			.map(Rule::from)
-			.collect();
+			.collect::<Result<_, _>>()?;
 
-		Self { name, rules }
+		Ok(Self { name, rules })
 	}
 }
 
 impl Rule {
-	pub fn from(s: &str) -> Self {
+	pub fn from(s: &str) -> Result<Self, Unexpected<'_>> {
 		if let Some((left, destination)) = s.split_once(":") {
-			for c in ['<', '>'] {
-				if let Some((dimension, threshold)) = left.split_once(c) {
-					let operand = dimension.chars().next().expect("Dimension should be one of x,m,a,s");
-					return Self {
-						operand: Some(PartAttribute::from(&operand)),
-						comparator: Some(Comparator::from(&c)),
-						threshold: threshold.parse().ok(),
-						out: String::from(destination)
-					}
-				}
-			}
-			panic!("Got rule in input which did not contain a '<' or '>'")
+			let i = left
+				.find(['<', '>'])
+				.ok_or_else(|| unexpected(left, "a condition containing '<' or '>'"))?;
 
+			Ok(Self {
+				operand: Some(PartAttribute::from(&left[..i])?),
+				comparator: Some(Comparator::from(&left[i..i + 1])?),
+				threshold: Some(parse_num(&left[i + 1..])?),
+				out: String::from(destination)
+			})
 		} else {
-			Self {
+			Ok(Self {
 				operand: None,
 				comparator: None,
 				threshold: None,
 				out: s.to_string()
-			}
+			})
 		}
 	}
 }
 
 impl Comparator {
-	pub fn from(c: &char) -> Self {
-		match c {
-			'<' => Self::LT,
-			'>' => Self::GT,
-			_ => panic!("Fatal - attempted to parse invalid input char '{}' as comparator", c.to_string())
+	pub fn from(s: &str) -> Result<Self, Unexpected<'_>> {
+		match s {
+			"<" => Ok(Self::LT),
+			">" => Ok(Self::GT),
+			_ => Err(unexpected(s, "a comparator ('<' or '>')"))
 		}
 	}
 }
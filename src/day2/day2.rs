@@ -1,32 +1,73 @@
-use std::error::Error;
-
-pub fn part1(lines: impl Iterator<Item = String>) -> u32 {
-    let red = 12;
-    let green = 13;
-    let blue = 14;
-
-    lines
-        .filter_map(|line| {
-            let game = Game::from(line);
-            game.possible_with(red, green, blue).then_some(game.id)
-        })
-        .sum()
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use crate::{
+    error::{unexpected, Result, Unexpected},
+    solution::Solution,
+    util::parse_num,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| Game::from(line).map_err(|err| err.locate(Self::DAY, input)))
+            .collect()
+    }
+
+    fn part1(games: &Self::Input) -> Result<impl Display> {
+        let limits = cubes(PART1_LIMITS);
+
+        Ok(games
+            .iter()
+            .filter_map(|game| game.possible_with(&limits).then_some(game.id))
+            .sum::<u32>())
+    }
+
+    fn part2(games: &Self::Input) -> Result<impl Display> {
+        let colours = colours(games);
+
+        Ok(games
+            .iter()
+            .map(|game| game.power(&colours))
+            .sum::<u32>())
+    }
+}
+
+/// The bag from part 1.
+pub const PART1_LIMITS: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+/// A number of cubes of each colour, keyed by colour name. Colours that aren't present count as 0.
+pub type Cubes = BTreeMap<String, u32>;
+
+pub fn cubes<'a>(counts: impl IntoIterator<Item = (&'a str, u32)>) -> Cubes {
+    counts
+        .into_iter()
+        .map(|(colour, count)| (String::from(colour), count))
+        .collect()
 }
 
-pub fn part2(lines: impl Iterator<Item = String>) -> u32 {
-    lines
-        .map(|line| {
-            let (r, g, b) = Game::from(line).min_possible();
-            r * g * b
-        })
-        .sum()
+/// Every colour that appears in any of the games.
+pub fn colours(games: &[Game]) -> BTreeSet<&str> {
+    games
+        .iter()
+        .flat_map(|game| &game.rounds)
+        .flat_map(|round| round.cubes.keys())
+        .map(String::as_str)
+        .collect()
 }
 
 #[derive(Debug)]
 pub struct Round {
-    red: u32,
-    green: u32,
-    blue: u32,
+    cubes: Cubes,
 }
 
 #[derive(Debug)]
@@ -36,54 +77,117 @@ pub struct Game {
 }
 
 impl Game {
-    pub fn from(line: String) -> Self {
+    pub fn from(line: &str) -> Result<Self, Unexpected<'_>> {
         let (prefix, rest) = line
             .split_once(": ")
-            .expect("Input stream contained malformed line!");
+            .ok_or_else(|| unexpected(line, "a line of the form 'Game [id]: [rounds]'"))?;
 
-        let id: u32 = prefix.split_once(" ").unwrap().1.parse().unwrap();
+        let id: u32 = prefix
+            .strip_prefix("Game ")
+            .ok_or_else(|| unexpected(prefix, "'Game [id]'"))
+            .and_then(parse_num)?;
 
         let rounds: Vec<Round> = rest
             .split("; ")
-            .map(|s| Round::from_comma_separated_colors(s).unwrap())
-            .collect();
+            .map(Round::from_comma_separated_colors)
+            .collect::<Result<_, _>>()?;
 
-        Self { id, rounds }
+        Ok(Self { id, rounds })
     }
 
-    pub fn possible_with(&self, red: u32, green: u32, blue: u32) -> bool {
-        let (max_r, max_g, max_b) = self.min_possible();
-        max_r <= red && max_g <= green && max_b <= blue
+    /// Whether every round could have been drawn from a bag holding `limits`.
+    pub fn possible_with(&self, limits: &Cubes) -> bool {
+        self.min_possible()
+            .iter()
+            .all(|(colour, count)| *count <= limits.get(colour).copied().unwrap_or(0))
     }
 
-    pub fn min_possible(&self) -> (u32, u32, u32) {
-        let mut r_max = 0;
-        let mut g_max = 0;
-        let mut b_max = 0;
+    /// The fewest cubes of each colour the bag could have held.
+    pub fn min_possible(&self) -> Cubes {
+        let mut maxima = Cubes::new();
 
-        for Round { red, green, blue } in &self.rounds {
-            r_max = r_max.max(*red);
-            g_max = g_max.max(*green);
-            b_max = b_max.max(*blue);
+        for round in &self.rounds {
+            for (colour, count) in &round.cubes {
+                let max = maxima.entry(colour.clone()).or_insert(0);
+                *max = (*max).max(*count);
+            }
         }
-        (r_max, g_max, b_max)
+        maxima
+    }
+
+    /// The product of `min_possible` across the given colours, any of which this game may never
+    /// have drawn.
+    pub fn power<'a>(&self, colours: impl IntoIterator<Item = &'a &'a str>) -> u32 {
+        let maxima = self.min_possible();
+        colours
+            .into_iter()
+            .map(|colour| maxima.get(*colour).copied().unwrap_or(0))
+            .product()
     }
 }
 
 impl Round {
-    pub fn from_comma_separated_colors(s: &str) -> Result<Self, Box<dyn Error>> {
-        let mut red = 0;
-        let mut green = 0;
-        let mut blue = 0;
+    pub fn from_comma_separated_colors(s: &str) -> Result<Self, Unexpected<'_>> {
+        let mut cubes = Cubes::new();
 
         for entry in s.split(", ") {
-            match entry.split_once(" ").unwrap() {
-                (n, "red") => red = n.parse()?,
-                (n, "green") => green = n.parse()?,
-                (n, "blue") => blue = n.parse()?,
-                _ => panic!("Got invalid color value in input!"),
-            }
+            let (n, colour) = entry
+                .split_once(' ')
+                .ok_or_else(|| unexpected(entry, "a count followed by a color"))?;
+
+            *cubes.entry(String::from(colour)).or_insert(0) += parse_num::<u32>(n)?;
         }
-        Ok(Self { red, green, blue })
+        Ok(Self { cubes })
+    }
+}
+
+/// The example from the puzzle description, shared by the tests for this day.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day2.run(EXAMPLE, Part::One).unwrap().answer, "8");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day2.run(EXAMPLE, Part::Two).unwrap().answer, "2286");
+    }
+
+    #[test]
+    fn reports_location_of_bad_count() {
+        let err = Day2::parse("Game 1: 3 blue\nGame 2: 1 blue, x purple\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 2, column 17: expected a number, found 'x'"
+        );
+    }
+
+    #[test]
+    fn supports_any_colours() {
+        let games = Day2::parse(
+            "Game 1: 2 purple, 1 red; 3 purple\nGame 2: 1 teal\nGame 3: 4 purple, 2 teal\n",
+        )
+        .unwrap();
+
+        let limits = cubes([("purple", 3), ("red", 1), ("teal", 2)]);
+        let possible = games.iter().filter(|game| game.possible_with(&limits));
+        assert_eq!(possible.map(|game| game.id).collect::<Vec<_>>(), [1, 2]);
+
+        assert_eq!(games[0].min_possible(), cubes([("purple", 3), ("red", 1)]));
+        assert_eq!(games[2].power(&["purple", "teal"]), 8);
+        assert_eq!(games[2].power(&colours(&games)), 0);
     }
 }
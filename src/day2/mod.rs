@@ -1,2 +1,3 @@
 pub mod day2;
-pub use day2::{part1, part2};
+pub mod query;
+pub use day2::Day2;
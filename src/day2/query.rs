@@ -0,0 +1,264 @@
+use std::{collections::BTreeMap, fmt};
+
+use super::day2::{colours, Cubes, Game};
+
+/// The ids of the games matching a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matches {
+    pub ids: Vec<u32>,
+}
+
+impl Matches {
+    fn from<'a>(games: impl Iterator<Item = &'a Game>) -> Self {
+        Self {
+            ids: games.map(|game| game.id).collect(),
+        }
+    }
+
+    pub fn id_sum(&self) -> u32 {
+        self.ids.iter().sum()
+    }
+}
+
+impl fmt::Display for Matches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} games (id sum {}): {:?}",
+            self.ids.len(),
+            self.id_sum(),
+            self.ids
+        )
+    }
+}
+
+/// Games that are possible with a bag holding `limits`.
+pub fn possible_with(games: &[Game], limits: &Cubes) -> Matches {
+    Matches::from(games.iter().filter(|game| game.possible_with(limits)))
+}
+
+/// Games that aren't possible with `limits`, but would be with `extra` more cubes of `colour`.
+pub fn newly_possible(games: &[Game], limits: &Cubes, colour: &str, extra: u32) -> Matches {
+    let mut raised = limits.clone();
+    let count = raised.entry(String::from(colour)).or_insert(0);
+    *count = count.saturating_add(extra);
+
+    Matches::from(
+        games
+            .iter()
+            .filter(|game| !game.possible_with(limits) && game.possible_with(&raised)),
+    )
+}
+
+/// A bag, along with the games it makes possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    pub limits: Cubes,
+    pub matches: Matches,
+}
+
+impl Bag {
+    pub fn total(&self) -> u64 {
+        total(&self.limits)
+    }
+}
+
+/// The number of cubes in a bag, which can be more than fits in a `u32`.
+fn total(cubes: &Cubes) -> u64 {
+    cubes.values().map(|count| *count as u64).sum()
+}
+
+/// The bag with the fewest cubes in total that makes at least `at_least` games possible, or `None`
+/// if there aren't that many games. Ties go to the bag that comes first in colour order.
+///
+/// Every limit worth trying for a colour is one of the games' minimum counts, so this tries every
+/// combination of those for all colours but the last, then takes the smallest limit for the last
+/// colour that admits enough of the remaining games. That's O(n^c log n) for n games and c colours,
+/// which is fine for a handful of colours.
+pub fn tightest_bag(games: &[Game], at_least: usize) -> Option<Bag> {
+    if at_least > games.len() {
+        return None;
+    }
+    if at_least == 0 {
+        return Some(Bag {
+            matches: possible_with(games, &Cubes::new()),
+            limits: Cubes::new(),
+        });
+    }
+
+    let colours = colours(games).into_iter().map(String::from).collect::<Vec<_>>();
+    let minima = games.iter().map(Game::min_possible).collect::<Vec<_>>();
+    let count = |cubes: &Cubes, colour: &str| cubes.get(colour).copied().unwrap_or(0);
+
+    let Some((last, others)) = colours.split_last() else {
+        // No cubes were ever drawn, so an empty bag makes every game possible.
+        return Some(Bag {
+            limits: Cubes::new(),
+            matches: Matches::from(games.iter()),
+        });
+    };
+
+    let candidates = others
+        .iter()
+        .map(|colour| {
+            let mut values = minima.iter().map(|cubes| count(cubes, colour)).collect::<Vec<_>>();
+            values.sort();
+            values.dedup();
+            values
+        })
+        .collect::<Vec<_>>();
+
+    let mut best: Option<Cubes> = None;
+    let mut choice = vec![0; others.len()];
+
+    loop {
+        let mut limits = others
+            .iter()
+            .zip(&choice)
+            .zip(&candidates)
+            .map(|((colour, i), values)| (colour.clone(), values[*i]))
+            .collect::<Cubes>();
+
+        let mut last_needed = minima
+            .iter()
+            .filter(|cubes| others.iter().all(|colour| count(cubes, colour) <= limits[colour]))
+            .map(|cubes| count(cubes, last))
+            .collect::<Vec<_>>();
+
+        if last_needed.len() >= at_least {
+            last_needed.sort();
+            limits.insert(last.clone(), last_needed[at_least - 1]);
+
+            if best.as_ref().is_none_or(|best| total(&limits) < total(best)) {
+                best = Some(limits);
+            }
+        }
+
+        // Advance to the next combination, like an odometer.
+        let Some(position) = (0..choice.len()).rev().find(|i| choice[*i] + 1 < candidates[*i].len())
+        else {
+            break;
+        };
+        choice[position] += 1;
+        choice[position + 1..].fill(0);
+    }
+
+    best.map(|limits| Bag {
+        matches: possible_with(games, &limits),
+        limits,
+    })
+}
+
+/// How the minimum number of cubes needed for one colour is spread across the games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pub min: u32,
+    pub median: u32,
+    pub max: u32,
+    pub mean: f64,
+    /// How many games needed each number of cubes.
+    pub counts: BTreeMap<u32, usize>,
+}
+
+impl Distribution {
+    fn from(mut values: Vec<u32>) -> Self {
+        values.sort();
+        let mut counts = BTreeMap::new();
+        for value in &values {
+            *counts.entry(*value).or_insert(0) += 1;
+        }
+
+        Self {
+            min: values[0],
+            median: values[values.len() / 2],
+            max: values[values.len() - 1],
+            mean: values.iter().sum::<u32>() as f64 / values.len() as f64,
+            counts,
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {}, median {}, max {}, mean {:.2}",
+            self.min, self.median, self.max, self.mean
+        )
+    }
+}
+
+/// The distribution of `Game::min_possible` for each colour, with games that never drew a colour
+/// counting as needing 0 of it.
+pub fn distributions(games: &[Game]) -> BTreeMap<String, Distribution> {
+    if games.is_empty() {
+        return BTreeMap::new();
+    }
+
+    let minima = games.iter().map(Game::min_possible).collect::<Vec<_>>();
+    colours(games)
+        .into_iter()
+        .map(|colour| {
+            let values = minima
+                .iter()
+                .map(|cubes| cubes.get(colour).copied().unwrap_or(0))
+                .collect();
+            (String::from(colour), Distribution::from(values))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        day2::day2::{cubes, EXAMPLE, PART1_LIMITS},
+        day2::Day2,
+        solution::Solution,
+    };
+
+    #[test]
+    fn raising_a_limit_admits_more_games() {
+        let games = Day2::parse(EXAMPLE).unwrap();
+
+        assert_eq!(possible_with(&games, &cubes(PART1_LIMITS)).ids, [1, 2, 5]);
+        assert!(newly_possible(&games, &cubes(PART1_LIMITS), "blue", 1).ids.is_empty());
+        assert_eq!(newly_possible(&games, &cubes(PART1_LIMITS), "red", 8).ids, [3]);
+    }
+
+    #[test]
+    fn raising_a_limit_saturates() {
+        let games = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(newly_possible(&games, &cubes(PART1_LIMITS), "red", u32::MAX).ids, [3]);
+    }
+
+    #[test]
+    fn totals_bags_beyond_u32() {
+        let games = Day2::parse("Game 1: 4294967295 red, 4294967295 blue\n").unwrap();
+        let bag = tightest_bag(&games, 1).unwrap();
+        assert_eq!(bag.total(), 2 * u32::MAX as u64);
+    }
+
+    #[test]
+    fn finds_tightest_bag() {
+        let games = Day2::parse(EXAMPLE).unwrap();
+
+        let bag = tightest_bag(&games, 3).unwrap();
+        assert_eq!(bag.limits, cubes([("blue", 6), ("green", 3), ("red", 6)]));
+        assert_eq!(bag.matches.ids, [1, 2, 5]);
+
+        assert_eq!(tightest_bag(&games, 5).unwrap().total(), 48);
+        assert_eq!(tightest_bag(&games, 6), None);
+    }
+
+    #[test]
+    fn summarises_each_colour() {
+        let games = Day2::parse(EXAMPLE).unwrap();
+        let distributions = distributions(&games);
+
+        let red = &distributions["red"];
+        assert_eq!((red.min, red.median, red.max), (1, 6, 20));
+        assert_eq!(red.mean, 9.0);
+        assert_eq!(distributions["blue"].counts, BTreeMap::from([(2, 1), (4, 1), (6, 2), (15, 1)]));
+    }
+}
@@ -1,186 +1,237 @@
-use std::collections::{HashMap, HashSet};
+use std::{fmt::Display, ops::Range};
 
-#[derive(Debug, Hash, Eq, PartialEq)]
-enum Tile {
+use crate::{
+    error::{Error, Result, Unexpected},
+    solution::Solution,
+    util::parse_num,
+};
+
+/// Index of a `Span` within its `Schematic`.
+pub type SpanId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
     Symbol(char),
-    Part(MachinePart),
+    /// One digit of a number, which is stored once as a span.
+    Digit(SpanId),
 }
 
-#[derive(Debug, Hash, Eq, PartialEq)]
-struct MachinePart {
-    pub part_number: u32,
+/// A number in the schematic, occupying the columns `start..end` of a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub number: u32,
+    pub row: usize,
     pub start: usize,
     pub end: usize,
-    pub row: usize,
 }
 
-impl MachinePart {
-    pub fn bounding_box(&self) -> impl Iterator<Item = (i32, i32)> {
-        let mut surrounding_points: Vec<(i32, i32)> = vec![];
-        let left = self.start as i32 - 1;
-        let right = (self.end + 1) as i32;
-
-        // to the left and to the right
-        surrounding_points.push((left, self.row as i32));
-        surrounding_points.push((right as i32, self.row as i32));
-
-        // above and below
-        for x in left..=right {
-            surrounding_points.push((x, self.row as i32 - 1));
-            surrounding_points.push((x, (self.row + 1) as i32));
-        }
-        surrounding_points.into_iter()
+impl Span {
+    pub fn columns(&self) -> Range<usize> {
+        self.start..self.end
     }
 }
 
+/// An engine schematic as a dense grid of cells, where each digit cell refers to the span of the
+/// number it's part of. Rows shorter than the longest one are padded with empty cells.
 #[derive(Debug)]
-struct Grid {
-    coordinates: HashMap<(usize, usize), Tile>,
+pub struct Schematic {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+    spans: Vec<Span>,
 }
 
-impl Grid {
-    pub fn from_lines(lines: impl Iterator<Item = String>) -> Self {
-        let mut coordinates: HashMap<(usize, usize), Tile> = HashMap::new();
-
-        for (y, line) in lines.enumerate() {
-            let mut x = 0;
-            let mut last_num_start_pos: Option<usize> = None;
-
-            loop {
-                let current_slice = &mut line[x..].chars();
-
-                if let Some(current_char) = current_slice.next() {
-                    if current_char.is_numeric() {
-                        if last_num_start_pos.is_none() {
-                            last_num_start_pos = Some(x);
-                        }
-                    } else {
-                        if current_char != '.' {
-                            coordinates.insert((x, y), Tile::Symbol(current_char));
-                        }
-                        if let Some(num_start) = last_num_start_pos {
-                            let part_number: u32 = String::from_iter(line[num_start..x].chars())
-                                .parse()
-                                .expect("Fatal - failed to parse supposed part number");
-
-                            for num_x in num_start..x {
-                                coordinates.insert(
-                                    (num_x, y),
-                                    Tile::Part(MachinePart {
-                                        part_number,
-                                        start: num_start,
-                                        end: x - 1,
-                                        row: y,
-                                    }),
-                                );
-                            }
-                            last_num_start_pos = None;
-                        }
-                    }
-                    x += 1;
-                } else {
-                    if let Some(num_start) = last_num_start_pos {
-                        let part_number: u32 = String::from_iter(line[num_start..x].chars())
-                            .parse()
-                            .expect("Fatal - failed to parse supposed part number");
-
-                        for num_x in num_start..x {
-                            coordinates.insert(
-                                (num_x, y),
-                                Tile::Part(MachinePart {
-                                    part_number,
-                                    start: num_start,
-                                    end: x - 1,
-                                    row: y,
-                                }),
-                            );
-                        }
-                    }
-
-                    break;
+impl Schematic {
+    pub fn from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, Unexpected<'a>> {
+        let lines = lines.collect::<Vec<_>>();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let height = lines.len();
+        let mut cells = vec![Cell::Empty; width * height];
+        let mut spans = vec![];
+
+        for (row, line) in lines.into_iter().enumerate() {
+            // Column and byte offset of the number currently being read, if any.
+            let mut number_start: Option<(usize, usize)> = None;
+
+            // The trailing '.' ends any number that runs to the end of the line.
+            let chars = line.char_indices().chain([(line.len(), '.')]).enumerate();
+
+            for (x, (offset, c)) in chars {
+                if c.is_ascii_digit() {
+                    number_start.get_or_insert((x, offset));
+                    cells[row * width + x] = Cell::Digit(spans.len());
+                    continue;
+                }
+
+                if let Some((start, start_offset)) = number_start.take() {
+                    spans.push(Span {
+                        number: parse_num(&line[start_offset..offset])?,
+                        row,
+                        start,
+                        end: x,
+                    });
+                }
+                if c != '.' {
+                    cells[row * width + x] = Cell::Symbol(c);
                 }
             }
         }
 
-        Self { coordinates }
+        Ok(Self {
+            width,
+            height,
+            cells,
+            spans,
+        })
     }
 
-    pub fn get(&self, x: usize, y: usize) -> Option<&Tile> {
-        self.coordinates.get(&(x, y))
+    pub fn width(&self) -> usize {
+        self.width
     }
 
-    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = &Tile> {
-        let mut neighbours: Vec<Option<&Tile>> = vec![];
-        for y0 in -1..=1 {
-            for x0 in -1..=1 {
-                if !(x == 0 && y == 0) {
-                    neighbours.push(self.get((x as i32 + x0) as usize, (y as i32 + y0) as usize))
-                }
-            }
-        }
-        neighbours.into_iter().flatten()
+    pub fn height(&self) -> usize {
+        self.height
     }
 
-    pub fn part_number_total(&self) -> u32 {
-        let mut total = 0;
-        let mut parts_uniq: HashSet<&MachinePart> = HashSet::new();
+    pub fn get(&self, x: usize, y: usize) -> Option<Cell> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
 
-        for (_, tile) in self.coordinates.iter() {
-            match tile {
-                Tile::Part(part) => {
-                    parts_uniq.insert(part);
-                }
-                _ => (),
-            }
-        }
-        for p in parts_uniq {
-            for (x, y) in p.bounding_box() {
-                match self.get(x as usize, y as usize) {
-                    Some(Tile::Symbol(c)) => {
-                        println!("{}: {}", p.part_number, c);
-                        total += p.part_number;
-                        break;
-                    }
-                    _ => {}
-                }
-            }
-        }
-        total
-    }
-
-    pub fn gear_ratio_total(&self) -> u32 {
-        let mut total = 0;
-
-        for ((x, y), tile) in self.coordinates.iter() {
-            match tile {
-                Tile::Symbol('*') => {
-                    let neighbouring_parts: HashSet<&u32> = self
-                        .neighbours(*x, *y)
-                        .filter_map(|n| match n {
-                            Tile::Part(MachinePart { part_number, .. }) => Some(part_number),
-                            _ => None,
-                        })
-                        .collect();
-                    if neighbouring_parts.len() == 2 {
-                        let mut ratio = 1;
-
-                        for val in neighbouring_parts {
-                            ratio *= val;
-                        }
-                        total += ratio;
-                    }
-                }
-                _ => (),
-            }
-        }
-        total
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    pub fn span(&self, id: SpanId) -> &Span {
+        &self.spans[id]
+    }
+
+    /// Every symbol, with its position, in reading order.
+    pub fn symbols(&self) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        self.cells.iter().enumerate().filter_map(|(i, cell)| match cell {
+            Cell::Symbol(c) => Some((i % self.width, i / self.width, *c)),
+            _ => None,
+        })
+    }
+
+    /// The positions of the (up to eight) cells surrounding `(x, y)` that lie within the grid.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let xs = x.saturating_sub(1)..=(x + 1).min(self.width.saturating_sub(1));
+        let ys = y.saturating_sub(1)..=(y + 1).min(self.height.saturating_sub(1));
+
+        ys.flat_map(move |ny| xs.clone().map(move |nx| (nx, ny)))
+            .filter(move |pos| *pos != (x, y))
+    }
+
+    /// The distinct spans touching `(x, y)`, in reading order. A span is counted once however
+    /// many of its digits it touches with, even if another span holds the same number.
+    pub fn adjacent_spans(&self, x: usize, y: usize) -> Vec<SpanId> {
+        let mut ids = self
+            .neighbours(x, y)
+            .filter_map(|(nx, ny)| match self.get(nx, ny) {
+                Some(Cell::Digit(id)) => Some(id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// The positions of the cells surrounding a span that lie within the grid.
+    pub fn span_neighbours(&self, id: SpanId) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let span = self.span(id);
+        let xs = span.start.saturating_sub(1)..(span.end + 1).min(self.width);
+        let ys = span.row.saturating_sub(1)..(span.row + 2).min(self.height);
+
+        ys.flat_map(move |y| xs.clone().map(move |x| (x, y)))
+            .filter(move |(x, y)| *y != span.row || !span.columns().contains(x))
+    }
+
+    /// Whether a span touches any symbol, and so is a part number.
+    pub fn is_part_number(&self, id: SpanId) -> bool {
+        self.span_neighbours(id)
+            .any(|(x, y)| matches!(self.get(x, y), Some(Cell::Symbol(_))))
+    }
+
+    pub fn part_number_total(&self) -> u32 {
+        (0..self.spans.len())
+            .filter(|id| self.is_part_number(*id))
+            .map(|id| self.spans[id].number)
+            .sum()
     }
 }
 
-pub fn part1(lines: impl Iterator<Item = String>) -> u32 {
-    Grid::from_lines(lines).part_number_total()
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Schematic::from_lines(input.lines()).map_err(|err| err.locate(Self::DAY, input))
+    }
+
+    fn part1(schematic: &Self::Input) -> Result<impl Display> {
+        Ok(schematic.part_number_total())
+    }
+
+    fn part2(schematic: &Self::Input) -> Result<impl Display> {
+        schematic.gear_ratio_total().ok_or_else(|| Error::NoSolution {
+            day: Self::DAY,
+            reason: String::from("the gear ratios add up to more than fits in a u128"),
+        })
+    }
 }
 
-pub fn part2(lines: impl Iterator<Item = String>) -> u32 {
-    Grid::from_lines(lines).gear_ratio_total()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day3.run(EXAMPLE, Part::One).unwrap().answer, "4361");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day3.run(EXAMPLE, Part::Two).unwrap().answer, "467835");
+    }
+
+    #[test]
+    fn numbers_at_the_edges_are_single_spans() {
+        let schematic = Day3::parse("12.\n..*\n.45\n#..").unwrap();
+
+        assert_eq!(
+            schematic.spans(),
+            [
+                Span { number: 12, row: 0, start: 0, end: 2 },
+                Span { number: 45, row: 2, start: 1, end: 3 },
+            ]
+        );
+        assert_eq!(schematic.get(2, 2), Some(Cell::Digit(1)));
+        assert_eq!(schematic.adjacent_spans(2, 1), [0, 1]);
+        assert_eq!(schematic.part_number_total(), 57);
+    }
+
+    #[test]
+    fn number_at_line_end_does_not_wrap_onto_next_row() {
+        let schematic = Day3::parse("..7\n*..").unwrap();
+        assert_eq!(schematic.part_number_total(), 0);
+    }
 }
@@ -0,0 +1,167 @@
+use super::day3::{Schematic, Span};
+
+/// How many distinct part numbers a symbol must touch to count as a gear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeighbourCount {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl NeighbourCount {
+    fn admits(&self, count: usize) -> bool {
+        match self {
+            NeighbourCount::Exactly(n) => count == *n,
+            NeighbourCount::AtLeast(n) => count >= *n,
+        }
+    }
+}
+
+/// How a gear's part numbers are combined into its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregate {
+    /// The combined value, or `None` if it doesn't fit in a `u128`.
+    fn apply(&self, mut numbers: impl Iterator<Item = u128>) -> Option<u128> {
+        match self {
+            Aggregate::Product => numbers.try_fold(1, u128::checked_mul),
+            Aggregate::Sum => numbers.try_fold(0, u128::checked_add),
+            Aggregate::Max => Some(numbers.max().unwrap_or(0)),
+        }
+    }
+}
+
+/// Which symbols count as gears, and how their value is worked out. The default is part 2's rule:
+/// a `*` touching exactly two part numbers, worth their product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearQuery {
+    symbols: Vec<char>,
+    neighbours: NeighbourCount,
+    aggregate: Aggregate,
+}
+
+impl Default for GearQuery {
+    fn default() -> Self {
+        Self {
+            symbols: vec!['*'],
+            neighbours: NeighbourCount::Exactly(2),
+            aggregate: Aggregate::Product,
+        }
+    }
+}
+
+impl GearQuery {
+    pub fn symbols(self, symbols: impl IntoIterator<Item = char>) -> Self {
+        Self {
+            symbols: symbols.into_iter().collect(),
+            ..self
+        }
+    }
+
+    pub fn neighbours(self, neighbours: NeighbourCount) -> Self {
+        Self { neighbours, ..self }
+    }
+
+    pub fn aggregate(self, aggregate: Aggregate) -> Self {
+        Self { aggregate, ..self }
+    }
+}
+
+/// A symbol matching a `GearQuery`, along with the part numbers touching it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gear {
+    pub x: usize,
+    pub y: usize,
+    pub symbol: char,
+    /// In reading order. Parts holding the same number are kept separate.
+    pub parts: Vec<Span>,
+    /// `None` if the value is too big for a `u128`, which takes a lot of large neighbours.
+    pub value: Option<u128>,
+}
+
+impl Schematic {
+    /// Every symbol matching the query, in reading order.
+    pub fn gears(&self, query: &GearQuery) -> Vec<Gear> {
+        self.symbols()
+            .filter(|(_, _, symbol)| query.symbols.contains(symbol))
+            .filter_map(|(x, y, symbol)| {
+                let parts = self
+                    .adjacent_spans(x, y)
+                    .into_iter()
+                    .map(|id| *self.span(id))
+                    .collect::<Vec<_>>();
+
+                query.neighbours.admits(parts.len()).then(|| Gear {
+                    x,
+                    y,
+                    symbol,
+                    value: query
+                        .aggregate
+                        .apply(parts.iter().map(|part| part.number as u128)),
+                    parts,
+                })
+            })
+            .collect()
+    }
+
+    /// The sum of every gear's ratio, or `None` if it doesn't fit in a `u128`.
+    pub fn gear_ratio_total(&self) -> Option<u128> {
+        self.gears(&GearQuery::default())
+            .iter()
+            .try_fold(0, |total: u128, gear| total.checked_add(gear.value?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day3::Day3, solution::Solution};
+
+    #[test]
+    fn parts_with_the_same_number_are_both_counted() {
+        let schematic = Day3::parse("5*5\n...").unwrap();
+        assert_eq!(schematic.gear_ratio_total(), Some(25));
+    }
+
+    #[test]
+    fn queries_other_symbols_and_counts() {
+        let schematic = Day3::parse("1.2.3\n.#.$.\n4...6").unwrap();
+
+        let hashes = schematic.gears(&GearQuery::default().symbols(['#']));
+        assert!(hashes.is_empty(), "'#' touches three parts, not two");
+
+        let query = GearQuery::default()
+            .symbols(['#', '$'])
+            .neighbours(NeighbourCount::AtLeast(3))
+            .aggregate(Aggregate::Sum);
+        let gears = schematic.gears(&query);
+
+        assert_eq!(gears.len(), 2);
+        assert_eq!((gears[0].x, gears[0].y, gears[0].symbol), (1, 1, '#'));
+        assert_eq!(
+            gears[0].parts.iter().map(|part| (part.number, part.row, part.start)).collect::<Vec<_>>(),
+            [(1, 0, 0), (2, 0, 2), (4, 2, 0)]
+        );
+        assert_eq!(gears[0].value, Some(7));
+        assert_eq!(gears[1].value, Some(11));
+
+        let max = schematic.gears(&query.aggregate(Aggregate::Max));
+        assert_eq!(max.iter().map(|gear| gear.value).collect::<Vec<_>>(), [Some(4), Some(6)]);
+    }
+
+    #[test]
+    fn aggregates_many_large_parts_without_overflowing() {
+        let schematic = Day3::parse("99999.99999\n99999*99999\n99999.99999").unwrap();
+        let query = GearQuery::default().neighbours(NeighbourCount::AtLeast(2));
+        assert_eq!(schematic.gears(&query)[0].value, Some(99999u128.pow(6)));
+
+        let wide = "4000000000.4000000000";
+        let schematic = Day3::parse(&format!("{}\n{}\n{}", wide, wide.replace('.', "*"), wide)).unwrap();
+        assert_eq!(schematic.gears(&query)[0].value, None);
+        assert_eq!(schematic.gears(&query.aggregate(Aggregate::Sum))[0].value, Some(24_000_000_000));
+    }
+}
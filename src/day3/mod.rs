@@ -1,2 +1,4 @@
 pub mod day3;
-pub use day3::{part1, part2};
+pub mod gears;
+pub mod render;
+pub use day3::Day3;
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use super::{
+    day3::{Cell, Schematic},
+    gears::GearQuery,
+};
+
+const RESET: &str = "\x1b[0m";
+const PART: &str = "\x1b[1;32m";
+const UNCONNECTED: &str = "\x1b[2m";
+const GEAR: &str = "\x1b[1;33m";
+const SYMBOL: &str = "\x1b[36m";
+const EMPTY: &str = "\x1b[90m";
+
+/// How a cell is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Empty,
+    /// A digit of a number that touches a symbol, and so counts towards `part_number_total`.
+    Part,
+    /// A digit of a number that doesn't touch any symbol.
+    Unconnected,
+    Symbol,
+    Gear,
+}
+
+impl Style {
+    fn class(&self) -> &'static str {
+        match self {
+            Style::Empty => "empty",
+            Style::Part => "part",
+            Style::Unconnected => "unconnected",
+            Style::Symbol => "symbol",
+            Style::Gear => "gear",
+        }
+    }
+}
+
+/// A row's characters and their styles, along with the column and value of each gear in it.
+struct Row {
+    cells: Vec<(char, Style)>,
+    gears: Vec<(usize, String)>,
+}
+
+fn rows(schematic: &Schematic, query: &GearQuery) -> Vec<Row> {
+    let is_part = (0..schematic.spans().len())
+        .map(|id| schematic.is_part_number(id))
+        .collect::<Vec<_>>();
+    let gears = schematic
+        .gears(query)
+        .into_iter()
+        .map(|gear| {
+            let value = gear.value.map_or_else(|| String::from("overflow"), |value| value.to_string());
+            ((gear.x, gear.y), value)
+        })
+        .collect::<HashMap<_, _>>();
+
+    (0..schematic.height())
+        .map(|y| {
+            let cells = (0..schematic.width())
+                .map(|x| match schematic.get(x, y) {
+                    Some(Cell::Digit(id)) => {
+                        let span = schematic.span(id);
+                        // Padded back out, in case the number was written with leading zeros.
+                        let digits = format!("{:0>1$}", span.number, span.columns().len());
+                        let digit = digits.chars().nth(x - span.start);
+                        let style = if is_part[id] { Style::Part } else { Style::Unconnected };
+                        (digit.unwrap_or('?'), style)
+                    }
+                    Some(Cell::Symbol(c)) if gears.contains_key(&(x, y)) => (c, Style::Gear),
+                    Some(Cell::Symbol(c)) => (c, Style::Symbol),
+                    _ => ('.', Style::Empty),
+                })
+                .collect();
+
+            let mut row_gears = gears
+                .iter()
+                .filter(|((_, gear_y), _)| *gear_y == y)
+                .map(|((x, _), value)| (*x, value.clone()))
+                .collect::<Vec<_>>();
+            row_gears.sort();
+
+            Row {
+                cells,
+                gears: row_gears,
+            }
+        })
+        .collect()
+}
+
+/// Draws the schematic with ANSI colours: part numbers in green, unconnected numbers dimmed and
+/// gears in yellow, with each row's gear values listed after it.
+pub fn render_ansi(schematic: &Schematic, query: &GearQuery) -> String {
+    let mut out = String::new();
+
+    for Row { cells, gears } in rows(schematic, query) {
+        for (c, style) in cells {
+            let colour = match style {
+                Style::Empty => EMPTY,
+                Style::Unconnected => UNCONNECTED,
+                Style::Part => PART,
+                Style::Symbol => SYMBOL,
+                Style::Gear => GEAR,
+            };
+            out.push_str(&format!("{}{}{}", colour, c, RESET));
+        }
+        for (x, value) in gears {
+            out.push_str(&format!("  {}{}={}{}", GEAR, x, value, RESET));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Draws the schematic as a standalone HTML page, with each gear's value in its tooltip and
+/// listed after its row.
+pub fn render_html(schematic: &Schematic, query: &GearQuery) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n\
+         .empty { color: #ccc; }\n\
+         .unconnected { color: #999; }\n\
+         .part { color: #080; font-weight: bold; }\n\
+         .symbol { color: #08a; }\n\
+         .gear { color: #b80; font-weight: bold; }\n\
+         .ratios { color: #b80; }\n\
+         </style>\n</head>\n<body>\n<pre>\n",
+    );
+
+    for Row { cells, gears } in rows(schematic, query) {
+        let values = gears.iter().cloned().collect::<HashMap<_, _>>();
+
+        for (x, (c, style)) in cells.into_iter().enumerate() {
+            let title = values
+                .get(&x)
+                .map(|value| format!(" title=\"{}\"", value))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<span class=\"{}\"{}>{}</span>",
+                style.class(),
+                title,
+                escape(c)
+            ));
+        }
+        if !gears.is_empty() {
+            let ratios = gears
+                .iter()
+                .map(|(x, value)| format!("{}={}", x, value))
+                .collect::<Vec<_>>();
+            out.push_str(&format!("  <span class=\"ratios\">{}</span>", ratios.join(" ")));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '&' => String::from("&amp;"),
+        '<' => String::from("&lt;"),
+        '>' => String::from("&gt;"),
+        '"' => String::from("&quot;"),
+        _ => c.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day3::Day3, solution::Solution};
+
+    #[test]
+    fn highlights_parts_and_gears() {
+        let schematic = Day3::parse("12*3\n....\n007&\n....\n5...").unwrap();
+        let ansi = render_ansi(&schematic, &GearQuery::default());
+        let lines = ansi.lines().collect::<Vec<_>>();
+
+        assert!(lines[0].starts_with(&format!("{PART}1{RESET}{PART}2{RESET}{GEAR}*{RESET}")));
+        assert!(lines[0].ends_with(&format!("  {GEAR}2=36{RESET}")));
+        assert!(lines[2].starts_with(&format!("{PART}0{RESET}{PART}0{RESET}{PART}7{RESET}")));
+        assert!(lines[2].ends_with(&format!("{SYMBOL}&{RESET}")));
+        assert!(lines[4].starts_with(&format!("{UNCONNECTED}5{RESET}")));
+    }
+
+    #[test]
+    fn renders_html() {
+        let schematic = Day3::parse("12*3\n..<.").unwrap();
+        let html = render_html(&schematic, &GearQuery::default());
+
+        assert!(html.contains("<span class=\"gear\" title=\"36\">*</span>"));
+        assert!(html.contains("<span class=\"symbol\">&lt;</span>"));
+        assert!(html.contains("<span class=\"ratios\">2=36</span>"));
+    }
+}
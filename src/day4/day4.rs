@@ -1,41 +1,45 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
+
+use super::scoring::{CopyWindow, ScoringRule};
+use crate::{
+    error::{end_of, unexpected, Error, Result, Unexpected},
+    solution::Solution,
+    util::parse_num,
+};
 
 #[derive(Debug, Clone)]
-struct Card {
+pub struct Card {
     card_number: u32,
     numbers_had: HashSet<u32>,
     winning_numbers: HashSet<u32>,
 }
 
 impl Card {
-    pub fn from(s: String) -> Option<Self> {
-        let (card_label, card_contents) = s.split_once(": ")?;
+    pub fn from(s: &str) -> Result<Self, Unexpected<'_>> {
+        let (card_label, card_contents) = s
+            .split_once(": ")
+            .ok_or_else(|| unexpected(s, "a line of the form 'Card [n]: [numbers] | [numbers]'"))?;
         let card_number: u32 = card_label
-            .strip_prefix("Card ")?
-            .trim_start()
-            .parse()
-            .expect("Error parsing card number from input line");
+            .strip_prefix("Card ")
+            .ok_or_else(|| unexpected(card_label, "'Card [n]'"))
+            .and_then(|n| parse_num(n.trim_start()))?;
 
-        let (left, right) = card_contents.split_once(" | ")?;
-        let numbers_had = split_on_spaces_and_parse_nums(left);
-        let winning_numbers = split_on_spaces_and_parse_nums(right);
+        let (left, right) = card_contents
+            .split_once(" | ")
+            .ok_or_else(|| unexpected(end_of(card_contents), "' | ' between the two sets of numbers"))?;
+        let numbers_had = split_on_spaces_and_parse_nums(left)?;
+        let winning_numbers = split_on_spaces_and_parse_nums(right)?;
 
-        Some(Self {
+        Ok(Self {
             card_number,
             numbers_had,
             winning_numbers,
         })
     }
 
-    pub fn value(&self) -> u32 {
-        let num_matches = self.match_count();
-        let multiplier: u32 = 2;
-
-        if num_matches > 0 {
-            multiplier.pow(num_matches - 1)
-        } else {
-            0
-        }
+    /// Points under part 1's doubling rule.
+    pub fn value(&self) -> u64 {
+        ScoringRule::Doubling.score(self.match_count())
     }
 
     pub fn match_count(&self) -> u32 {
@@ -46,35 +50,136 @@ impl Card {
     }
 }
 
-fn split_on_spaces_and_parse_nums(s: &str) -> HashSet<u32> {
-    s.split(" ")
-        .map(|number_str| number_str.parse())
-        .flatten()
-        .collect()
+fn split_on_spaces_and_parse_nums(s: &str) -> Result<HashSet<u32>, Unexpected<'_>> {
+    s.split_whitespace().map(parse_num).collect()
 }
 
-pub fn part1(lines: impl Iterator<Item = String>) -> u32 {
-    lines
-        .map(|line| Card::from(line).map(|card| card.value()))
-        .flatten()
-        .sum()
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| Card::from(line).map_err(|err| err.locate(Self::DAY, input)))
+            .collect()
+    }
+
+    fn part1(cards: &Self::Input) -> Result<impl Display> {
+        Ok(ScoringRule::Doubling.total(cards))
+    }
+
+    fn part2(cards: &Self::Input) -> Result<impl Display> {
+        total_cards_won(cards, &CopyWindow::default())
+    }
 }
 
-pub fn part2(lines: impl Iterator<Item = String>) -> usize {
-    let mut cards: Vec<Card> = lines.map(Card::from).flatten().collect();
-    let mut i = 0;
+/// Counts the original cards plus every copy won, by working down the cards once while tracking
+/// how many copies of each are held. Each copy of a card wins one more copy of each card in its
+/// window.
+pub fn total_cards_won(cards: &[Card], window: &CopyWindow) -> Result<u128> {
+    let mut copies = vec![1u128; cards.len()];
 
-    loop {
-        if i == cards.len() {
-            break;
+    for (i, card) in cards.iter().enumerate() {
+        let mut won = window.won(i, card.match_count())?;
+        // A card with no matches wins nothing, wherever its empty window would start.
+        if !won.is_empty() && won.end > cards.len() {
+            if !window.truncate {
+                return Err(no_solution(format!(
+                    "card {} wins copies of cards up to position {}, but there are only {} cards",
+                    card.card_number,
+                    won.end,
+                    cards.len()
+                )));
+            }
+            won.end = cards.len();
         }
-        let card = cards[i].clone();
-        let start = card.card_number + 1;
-        for j in start..(start + card.match_count()) {
-            let won_card = cards[(j - 1) as usize].clone();
-            cards.push(won_card);
+
+        for j in won {
+            copies[j] = copies[j]
+                .checked_add(copies[i])
+                .ok_or_else(|| no_solution(String::from("the number of cards won overflows a u128")))?;
         }
-        i += 1;
     }
-    cards.len()
+
+    copies
+        .into_iter()
+        .try_fold(0u128, u128::checked_add)
+        .ok_or_else(|| no_solution(String::from("the number of cards won overflows a u128")))
+}
+
+pub(super) fn no_solution(reason: String) -> Error {
+    Error::NoSolution {
+        day: Day4::DAY,
+        reason,
+    }
+}
+
+/// The example from the puzzle description, shared by the tests for this day.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day4.run(EXAMPLE, Part::One).unwrap().answer, "13");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day4.run(EXAMPLE, Part::Two).unwrap().answer, "30");
+    }
+
+    /// Every card wins copies of the next two, so the number of copies grows like the Fibonacci
+    /// numbers, apart from the last two cards.
+    fn cascading_cards(count: usize) -> String {
+        (1..=count)
+            .map(|n| {
+                let had = if n + 2 <= count { "1 2" } else { "3 4" };
+                format!("Card {}: {} | 1 2\n", n, had)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn counts_copies_beyond_u64() {
+        let cards = Day4::parse(&cascading_cards(100)).unwrap();
+        assert!(total_cards_won(&cards, &CopyWindow::default()).unwrap() > u64::MAX as u128);
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let cards = Day4::parse(&cascading_cards(200)).unwrap();
+        assert_eq!(
+            total_cards_won(&cards, &CopyWindow::default()).unwrap_err().to_string(),
+            "day 4: no solution: the number of cards won overflows a u128"
+        );
+    }
+
+    #[test]
+    fn reports_copies_past_the_last_card() {
+        let cards = Day4::parse("Card 1: 1 | 1\n").unwrap();
+        assert!(total_cards_won(&cards, &CopyWindow::default()).is_err());
+    }
+
+    #[test]
+    fn ignores_empty_windows_past_the_last_card() {
+        let cards = Day4::parse("Card 1: 1 | 1\nCard 2: 2 | 3\nCard 3: 4 | 5\n").unwrap();
+        let window = CopyWindow { offset: 2, ..CopyWindow::default() };
+        assert_eq!(total_cards_won(&cards, &window).unwrap(), 4);
+    }
 }
@@ -1,2 +1,3 @@
 pub mod day4;
-pub use day4::{part1,part2};
+pub mod scoring;
+pub use day4::Day4;
@@ -0,0 +1,233 @@
+use std::{fmt, str::FromStr};
+
+use super::day4::{no_solution, total_cards_won, Card};
+use crate::error::Result;
+
+/// How many points a card is worth, given how many of its numbers match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoringRule {
+    /// 1 point for the first match, doubled for each one after (part 1).
+    Doubling,
+    /// 1 point per match.
+    Linear,
+    /// 1, 2, 3, 5, 8, ... points for 1, 2, 3, 4, 5, ... matches.
+    Fibonacci,
+    /// The points for 0, 1, 2, ... matches, with any more matches than that worth the last entry.
+    Table(Vec<u64>),
+}
+
+impl ScoringRule {
+    pub fn score(&self, matches: u32) -> u64 {
+        match self {
+            ScoringRule::Doubling => match matches {
+                0 => 0,
+                n => 1u64.checked_shl(n - 1).unwrap_or(u64::MAX),
+            },
+            ScoringRule::Linear => matches as u64,
+            ScoringRule::Fibonacci => match matches {
+                0 => 0,
+                n => {
+                    let (mut a, mut b) = (1u64, 2u64);
+                    for _ in 1..n {
+                        (a, b) = (b, a.saturating_add(b));
+                    }
+                    a
+                }
+            },
+            ScoringRule::Table(points) => points
+                .get(matches as usize)
+                .or(points.last())
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+
+    /// The total score of a set of cards.
+    pub fn total(&self, cards: &[Card]) -> u64 {
+        cards
+            .iter()
+            .map(|card| self.score(card.match_count()))
+            .fold(0, u64::saturating_add)
+    }
+}
+
+/// Reads `doubling`, `linear`, `fibonacci` or `table:0,1,3,6`.
+impl FromStr for ScoringRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "doubling" => Ok(ScoringRule::Doubling),
+            "linear" => Ok(ScoringRule::Linear),
+            "fibonacci" => Ok(ScoringRule::Fibonacci),
+            _ => s
+                .strip_prefix("table:")
+                .ok_or_else(|| format!("unknown scoring rule '{}'", s))?
+                .split(',')
+                .map(|points| {
+                    points
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("expected a number of points, found '{}'", points))
+                })
+                .collect::<std::result::Result<_, _>>()
+                .map(ScoringRule::Table),
+        }
+    }
+}
+
+impl fmt::Display for ScoringRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoringRule::Doubling => write!(f, "doubling"),
+            ScoringRule::Linear => write!(f, "linear"),
+            ScoringRule::Fibonacci => write!(f, "fibonacci"),
+            ScoringRule::Table(points) => {
+                let points = points.iter().map(u64::to_string).collect::<Vec<_>>();
+                write!(f, "table:{}", points.join(","))
+            }
+        }
+    }
+}
+
+/// Which cards a card with `n` matches wins copies of: `n * per_match` cards (at most `max`),
+/// starting `offset` cards after it. Part 2 wins the next `n` cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyWindow {
+    pub offset: usize,
+    pub per_match: usize,
+    pub max: Option<usize>,
+    /// Whether copies of cards past the end are silently dropped, rather than being an error.
+    pub truncate: bool,
+}
+
+impl Default for CopyWindow {
+    fn default() -> Self {
+        Self {
+            offset: 1,
+            per_match: 1,
+            max: None,
+            truncate: false,
+        }
+    }
+}
+
+impl CopyWindow {
+    /// The positions of the cards won by the card at position `i`, which may run past the end.
+    /// Fails if they run past the end of `usize`.
+    pub fn won(&self, i: usize, matches: u32) -> Result<std::ops::Range<usize>> {
+        let overflow = || no_solution(format!("the copies won by card position {} overflow a usize", i));
+
+        let len = match (matches as usize).checked_mul(self.per_match) {
+            Some(len) => self.max.map_or(len, |max| len.min(max)),
+            None => self.max.ok_or_else(overflow)?,
+        };
+        let start = i.checked_add(self.offset).ok_or_else(overflow)?;
+        let end = start.checked_add(len).ok_or_else(overflow)?;
+        Ok(start..end)
+    }
+}
+
+/// The results of scoring one set of cards under one variant of the rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scores {
+    pub points: u64,
+    pub cards_won: u128,
+}
+
+/// Scores the same cards under each combination of scoring rule and copy window.
+pub fn score_variants(
+    cards: &[Card],
+    variants: &[(ScoringRule, CopyWindow)],
+) -> Vec<Result<Scores>> {
+    variants
+        .iter()
+        .map(|(rule, window)| {
+            Ok(Scores {
+                points: rule.total(cards),
+                cards_won: total_cards_won(cards, window)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        day4::{day4::EXAMPLE, Day4},
+        solution::Solution,
+    };
+
+    #[test]
+    fn scores_matches() {
+        let scores = |rule: ScoringRule| (0..=6).map(|n| rule.score(n)).collect::<Vec<_>>();
+
+        assert_eq!(scores(ScoringRule::Doubling), [0, 1, 2, 4, 8, 16, 32]);
+        assert_eq!(scores(ScoringRule::Linear), [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(scores(ScoringRule::Fibonacci), [0, 1, 2, 3, 5, 8, 13]);
+        assert_eq!(scores("table:0,10,15".parse().unwrap()), [0, 10, 15, 15, 15, 15, 15]);
+    }
+
+    #[test]
+    fn scores_example_under_several_variants() {
+        let cards = Day4::parse(EXAMPLE).unwrap();
+        let truncated = CopyWindow {
+            per_match: 2,
+            truncate: true,
+            ..CopyWindow::default()
+        };
+
+        let results = score_variants(
+            &cards,
+            &[
+                (ScoringRule::Doubling, CopyWindow::default()),
+                (ScoringRule::Linear, truncated),
+                (ScoringRule::Fibonacci, CopyWindow::default()),
+            ],
+        );
+
+        let results = results.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(results[0], Scores { points: 13, cards_won: 30 });
+        assert_eq!(results[1].points, 9);
+        assert_eq!(results[2].points, 10);
+    }
+
+    #[test]
+    fn reports_windows_overflowing_usize() {
+        let cards = Day4::parse(EXAMPLE).unwrap();
+        let huge = |window: CopyWindow| total_cards_won(&cards, &window).unwrap_err().to_string();
+
+        assert_eq!(
+            huge(CopyWindow {
+                per_match: usize::MAX,
+                ..CopyWindow::default()
+            }),
+            "day 4: no solution: the copies won by card position 0 overflow a usize"
+        );
+        assert_eq!(
+            huge(CopyWindow {
+                offset: usize::MAX,
+                truncate: true,
+                ..CopyWindow::default()
+            }),
+            "day 4: no solution: the copies won by card position 0 overflow a usize"
+        );
+
+        let capped = CopyWindow {
+            per_match: usize::MAX,
+            max: Some(1),
+            truncate: true,
+            ..CopyWindow::default()
+        };
+        assert!(total_cards_won(&cards, &capped).is_ok());
+    }
+
+    #[test]
+    fn parses_and_prints_rules() {
+        for rule in ["doubling", "linear", "fibonacci", "table:0,1,3,6"] {
+            assert_eq!(rule.parse::<ScoringRule>().unwrap().to_string(), rule);
+        }
+        assert!("table:1,x".parse::<ScoringRule>().is_err());
+    }
+}
@@ -1,59 +1,236 @@
 use std::{
 	collections::HashMap,
-	io::{BufRead, BufReader, Read},
+	fmt::{self, Display},
 };
 
-#[derive(Debug)]
-struct Range {
+use crate::{
+	error::{end_of, unexpected, Error, Result, Unexpected},
+	solution::Solution,
+	util::parse_num,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
 	start: u64,
 	end: u64,
 }
 
-#[derive(Debug)]
-struct SeedMap {
+/// A map from one category to another, as a list of entries sorted by source start. Numbers
+/// outside every entry map to themselves.
+#[derive(Debug, Clone)]
+pub struct SeedMap {
 	pub source: String,
 	pub dest: String,
 	ranges: Vec<(Range, Range)>,
 }
 
+/// How `SeedMap::parse` treats entries that are empty, overflow `u64`, or overlap each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Validation {
+	/// Report problems but carry on: empty entries are dropped, overflowing ones are cut short
+	/// at `u64::MAX`, and where source ranges overlap the entry listed first wins.
+	#[default]
+	Lenient,
+	/// Reject the first problem as a parse error.
+	Strict,
+}
+
 impl Range {
 	pub fn includes(&self, num: u64) -> bool {
 		num >= self.start && num < self.end
 	}
 
-	pub fn pair_from(line: &str) -> (Self, Self) {
-		let parts: Vec<u64> = line
-			.split_whitespace()
-			.take(3)
-			.filter_map(|part| part.parse::<u64>().ok())
-			.collect();
+	pub fn overlaps(&self, other: &Range) -> bool {
+		self.start < other.end && other.start < self.end
+	}
 
-		if let [dest_range_start, src_range_start, len] = &parts[..] {
-			let dest_range = Self {
-				start: *dest_range_start,
-				end: dest_range_start + len,
-			};
-			let src_range = Self {
-				start: *src_range_start,
-				end: src_range_start + len,
-			};
-			(src_range, dest_range)
-		} else {
-			panic!("Malformed line")
+	pub fn pair_from(line: &str) -> Result<(Self, Self), Unexpected<'_>> {
+		Entry::from(line).map(|entry| entry.ranges())
+	}
+
+	/// The parts of this range not covered by any of `covered`, in order.
+	fn without(&self, covered: &[Range]) -> Vec<Range> {
+		let mut covered: Vec<_> = covered.iter().filter(|range| range.overlaps(self)).collect();
+		covered.sort_by_key(|range| range.start);
+
+		let mut pieces = vec![];
+		let mut pos = self.start;
+		for range in covered {
+			if pos < range.start {
+				pieces.push(Range { start: pos, end: range.start });
+			}
+			pos = pos.max(range.end);
 		}
+		if pos < self.end {
+			pieces.push(Range { start: pos, end: self.end });
+		}
+		pieces
+	}
+}
+
+/// One `[destination start] [source start] [length]` line of a map, before validation.
+struct Entry<'a> {
+	line: &'a str,
+	dest_start: u64,
+	src_start: u64,
+	len: u64,
+}
+
+impl<'a> Entry<'a> {
+	fn from(line: &'a str) -> Result<Self, Unexpected<'a>> {
+		match split_on_spaces_and_parse_nums(line)?[..] {
+			[dest_start, src_start, len] => Ok(Self {
+				line,
+				dest_start,
+				src_start,
+				len,
+			}),
+			_ => Err(unexpected(line, "three numbers: [destination start] [source start] [length]")),
+		}
+	}
+
+	fn overflows(&self) -> bool {
+		self.src_start.checked_add(self.len).is_none() || self.dest_start.checked_add(self.len).is_none()
+	}
+
+	/// The source and destination ranges, cut short so that neither runs past `u64::MAX`.
+	fn ranges(&self) -> (Range, Range) {
+		let len = self.len.min(u64::MAX - self.src_start).min(u64::MAX - self.dest_start);
+		(
+			Range {
+				start: self.src_start,
+				end: self.src_start + len,
+			},
+			Range {
+				start: self.dest_start,
+				end: self.dest_start + len,
+			},
+		)
 	}
 }
 
 impl SeedMap {
 	pub fn lookup(&self, num: u64) -> u64 {
-		if let Some((src, dest)) = self.ranges.iter().find(|(src, _)| src.includes(num)) {
-			let offset = num - src.start;
-			dest.start + offset
-		} else {
-			num
+		let i = self.ranges.partition_point(|(src, _)| src.start <= num);
+		match i.checked_sub(1).map(|i| &self.ranges[i]) {
+			Some((src, dest)) if src.includes(num) => dest.start + (num - src.start),
+			_ => num,
+		}
+	}
+
+	/// Maps a set of ranges through this map, splitting them wherever they cross the edge of an
+	/// entry. Parts that don't fall in any entry map to themselves.
+	pub fn map_ranges(&self, ranges: &[Range]) -> Vec<Range> {
+		let mut mapped = vec![];
+
+		for range in ranges {
+			let mut pos = range.start;
+			let first = self.ranges.partition_point(|(src, _)| src.end <= range.start);
+
+			for (src, dest) in self.ranges[first..].iter().take_while(|(src, _)| src.start < range.end) {
+				if pos < src.start {
+					mapped.push(Range { start: pos, end: src.start });
+				}
+				let start = pos.max(src.start);
+				let end = range.end.min(src.end);
+				mapped.push(Range {
+					start: dest.start + (start - src.start),
+					end: dest.start + (end - src.start),
+				});
+				pos = end;
+			}
+			if pos < range.end {
+				mapped.push(Range { start: pos, end: range.end });
+			}
+		}
+
+		mapped
+	}
+
+	/// Composes this map with `next`, which maps from this map's destination, into a single map
+	/// straight from this map's source to `next`'s destination. Looking a number up in the result
+	/// is the same as looking it up here and then in `next`.
+	pub fn compose(&self, next: &SeedMap) -> SeedMap {
+		let next_pieces = next.pieces();
+		let mut ranges = vec![];
+
+		for (src, dest) in self.pieces() {
+			let first = next_pieces.partition_point(|(next_src, _)| next_src.end <= dest.start);
+
+			for (next_src, next_dest) in next_pieces[first..]
+				.iter()
+				.take_while(|(next_src, _)| next_src.start < dest.end)
+			{
+				let start = dest.start.max(next_src.start);
+				let len = dest.end.min(next_src.end) - start;
+				let from = src.start + (start - dest.start);
+				let to = next_dest.start + (start - next_src.start);
+
+				// Pieces that end up where they started are covered by the identity default.
+				if from != to {
+					ranges.push((Range { start: from, end: from + len }, Range { start: to, end: to + len }));
+				}
+			}
+		}
+
+		ranges.sort_by_key(|(src, _)| src.start);
+		SeedMap {
+			source: self.source.clone(),
+			dest: next.dest.clone(),
+			ranges,
 		}
 	}
 
+	/// The same map going the other way, from destination back to source. Like `reverse_lookup`,
+	/// numbers outside every entry's destination map to themselves, and where destinations
+	/// overlap the entry with the lowest source wins.
+	pub fn invert(&self) -> SeedMap {
+		let mut ranges = vec![];
+		let mut covered = vec![];
+
+		for (src, dest) in &self.ranges {
+			ranges.extend(uncovered_pieces(*dest, *src, &covered));
+			covered.push(*dest);
+		}
+		ranges.sort_by_key(|(src, _)| src.start);
+
+		SeedMap {
+			source: self.dest.clone(),
+			dest: self.source.clone(),
+			ranges,
+		}
+	}
+
+	/// The entries along with the gaps between them as identity entries, covering every number
+	/// below `u64::MAX` in order.
+	fn pieces(&self) -> Vec<(Range, Range)> {
+		let mut pieces = vec![];
+		let mut pos = 0;
+
+		for (src, dest) in &self.ranges {
+			let start = pos.max(src.start);
+			if start >= src.end {
+				continue;
+			}
+			if pos < start {
+				pieces.push((Range { start: pos, end: start }, Range { start: pos, end: start }));
+			}
+			pieces.push((
+				Range { start, end: src.end },
+				Range {
+					start: dest.start + (start - src.start),
+					end: dest.end,
+				},
+			));
+			pos = src.end;
+		}
+		if pos < u64::MAX {
+			pieces.push((Range { start: pos, end: u64::MAX }, Range { start: pos, end: u64::MAX }));
+		}
+
+		pieces
+	}
+
 	pub fn reverse_lookup(&self, num: u64) -> u64 {
 		if let Some((src, dest)) = self.ranges.iter().find(|(_, dest)| dest.includes(num)) {
 			let offset = num - dest.start;
@@ -63,139 +240,491 @@ impl SeedMap {
 		}
 	}
 
-	pub fn from(section: &str) -> Self {
+	pub fn from(section: &str) -> Result<Self, Unexpected<'_>> {
+		Self::parse(section, Validation::Lenient).map(|(map, _)| map)
+	}
+
+	/// Parses a section, along with any problems found in its entries. In strict mode the first
+	/// problem is returned as an error instead.
+	pub fn parse(
+		section: &str,
+		validation: Validation,
+	) -> Result<(Self, Vec<Unexpected<'_>>), Unexpected<'_>> {
 		let lines: Vec<&str> = section.lines().collect();
-		let header = lines.first().expect("Section header should not be empty");
+		let header = lines.first().copied().unwrap_or(section).trim();
 
 		let (source, dest) = header
-			.trim()
 			.strip_suffix(" map:")
 			.and_then(|rest| rest.split_once("-to-"))
-			.expect("Section header should be of the form '[source]-to-[destination] map:'");
+			.ok_or_else(|| unexpected(header, "a section header of the form '[source]-to-[destination] map:'"))?;
+
+		let entries = lines
+			.into_iter()
+			.skip(1)
+			.map(Entry::from)
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mut problems = vec![];
+		let mut kept: Vec<(&str, Range, Range)> = vec![];
+		let mut ranges = vec![];
+
+		for entry in &entries {
+			if entry.len == 0 {
+				problems.push(unexpected(entry.line, "an entry with a non-zero length"));
+				continue;
+			}
+			if entry.overflows() {
+				problems.push(unexpected(entry.line, "an entry whose ranges end within u64"));
+			}
+
+			let (src, dest) = entry.ranges();
+			if let Some((line, ..)) = kept.iter().find(|(_, earlier, _)| earlier.overlaps(&src)) {
+				let expected = format!("a source range that doesn't overlap '{}'", line);
+				problems.push(unexpected(entry.line, expected));
+			}
+			if let Some((line, ..)) = kept.iter().find(|(.., earlier)| earlier.overlaps(&dest)) {
+				let expected = format!("a destination range that doesn't overlap '{}'", line);
+				problems.push(unexpected(entry.line, expected));
+			}
 
-		let ranges = lines.into_iter().skip(1).map(Range::pair_from).collect();
+			// Earlier entries take precedence, so only keep what they don't already cover.
+			let covered: Vec<_> = kept.iter().map(|(_, earlier, _)| *earlier).collect();
+			ranges.extend(uncovered_pieces(src, dest, &covered));
+			kept.push((entry.line, src, dest));
+		}
+
+		if validation == Validation::Strict {
+			if let Some(problem) = problems.into_iter().next() {
+				return Err(problem);
+			}
+			problems = vec![];
+		}
 
-		Self {
+		ranges.sort_by_key(|(src, _): &(Range, Range)| src.start);
+		let map = Self {
 			source: String::from(source),
 			dest: String::from(dest),
 			ranges,
+		};
+		Ok((map, problems))
+	}
+}
+
+/// The parts of the entry from `src` to `dest` whose source isn't already `covered`, each paired
+/// with the matching part of `dest`.
+fn uncovered_pieces(
+	src: Range,
+	dest: Range,
+	covered: &[Range],
+) -> impl Iterator<Item = (Range, Range)> + '_ {
+	src.without(covered).into_iter().map(move |piece| {
+		let offset = piece.start - src.start;
+		(
+			piece,
+			Range {
+				start: dest.start + offset,
+				end: dest.start + offset + (piece.end - piece.start),
+			},
+		)
+	})
+}
+
+/// Prints the map in the same format it's parsed from.
+impl Display for SeedMap {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}-to-{} map:", self.source, self.dest)?;
+		for (src, dest) in &self.ranges {
+			write!(f, "\n{} {} {}", dest.start, src.start, src.end - src.start)?;
 		}
+		Ok(())
 	}
 }
 
-fn split_on_spaces_and_parse_nums(s: &str) -> Vec<u64> {
-	s.split_whitespace()
-		.map(|number_str| number_str.parse())
-		.flatten()
-		.collect()
+fn split_on_spaces_and_parse_nums(s: &str) -> Result<Vec<u64>, Unexpected<'_>> {
+	s.split_whitespace().map(parse_num).collect()
 }
 
-fn read_seed_nums(reader: &mut BufReader<Box<dyn Read>>) -> Vec<u64> {
-	let mut header = String::new();
+pub struct Almanac {
+	seed_nums: Vec<u64>,
+	seed_maps: Vec<SeedMap>,
+}
 
-	reader
-		.read_line(&mut header)
-		.expect("Input should start with a header specifying seed numbers");
+impl Almanac {
+	/// Parses the whole almanac, along with any problems found in the maps' entries, as for
+	/// `SeedMap::parse`.
+	pub fn parse(input: &str, validation: Validation) -> Result<(Self, Vec<Error>)> {
+		let sections = input.trim_end();
+		let (header, rest) = sections
+			.split_once("\n\n")
+			.unwrap_or((sections, end_of(sections)));
 
+		let parsed = read_seed_nums(header).and_then(|seed_nums| {
+			let (seed_maps, problems) = read_seed_maps(rest, validation)?;
+			Ok((Almanac { seed_nums, seed_maps }, problems))
+		});
+		let (almanac, problems) = parsed.map_err(|err| err.locate(Day5::DAY, input))?;
+
+		let problems = problems
+			.into_iter()
+			.map(|problem| problem.locate(Day5::DAY, input))
+			.collect();
+		Ok((almanac, problems))
+	}
+
+	/// The maps leading from one category to another, in order. The maps form a graph between
+	/// categories, which must have exactly one route from `from` to `to` and no cycles along the
+	/// way.
+	pub fn maps_between(&self, from: &str, to: &str) -> Result<Vec<&SeedMap>> {
+		let mut edges: HashMap<&str, Vec<&SeedMap>> = HashMap::new();
+		for map in &self.seed_maps {
+			edges.entry(map.source.as_str()).or_default().push(map);
+		}
+
+		let mut routes = vec![];
+		find_routes(&edges, &mut vec![from], &mut vec![], to, &mut routes)?;
+
+		match &routes[..] {
+			[route] => Ok(route.clone()),
+			[] if !edges.contains_key(from) && from != to => {
+				Err(no_solution(format!("there is no map from '{}'", from)))
+			}
+			[] => Err(no_solution(format!("there is no chain of maps from '{}' to '{}'", from, to))),
+			[first, second, ..] => Err(no_solution(format!(
+				"there is more than one chain of maps from '{}' to '{}': {} and {}",
+				from,
+				to,
+				describe_route(from, first),
+				describe_route(from, second),
+			))),
+		}
+	}
+
+	/// Every map from one category to another, composed into one.
+	pub fn map_between(&self, from: &str, to: &str) -> Result<SeedMap> {
+		let identity = SeedMap {
+			source: String::from(from),
+			dest: String::from(from),
+			ranges: vec![],
+		};
+
+		Ok(self
+			.maps_between(from, to)?
+			.into_iter()
+			.fold(identity, |composed, map| composed.compose(map)))
+	}
+
+	pub fn seed_to_location(&self) -> Result<SeedMap> {
+		self.map_between("seed", "location")
+	}
+}
+
+/// Walks every route out of the last category in `path`, collecting those that reach `to`. Stops
+/// early once there are two, since that's already ambiguous.
+fn find_routes<'a: 'p, 'p>(
+	edges: &HashMap<&str, Vec<&'a SeedMap>>,
+	path: &mut Vec<&'p str>,
+	maps: &mut Vec<&'a SeedMap>,
+	to: &str,
+	routes: &mut Vec<Vec<&'a SeedMap>>,
+) -> Result<()> {
+	let current = path[path.len() - 1];
+	if current == to {
+		routes.push(maps.clone());
+		return Ok(());
+	}
+
+	for map in edges.get(current).into_iter().flatten() {
+		if let Some(i) = path.iter().position(|category| *category == map.dest) {
+			return Err(no_solution(format!(
+				"the maps form a cycle: {} -> {}",
+				path[i..].join(" -> "),
+				map.dest
+			)));
+		}
+
+		path.push(&map.dest);
+		maps.push(map);
+		find_routes(edges, path, maps, to, routes)?;
+		path.pop();
+		maps.pop();
+
+		if routes.len() > 1 {
+			break;
+		}
+	}
+	Ok(())
+}
+
+fn describe_route(from: &str, route: &[&SeedMap]) -> String {
+	route.iter().fold(String::from(from), |description, map| format!("{} -> {}", description, map.dest))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+	const DAY: u8 = 5;
+
+	type Input = Almanac;
+
+	fn parse(input: &str) -> Result<Self::Input> {
+		Almanac::parse(input, Validation::Lenient).map(|(almanac, _)| almanac)
+	}
+
+	fn part1(almanac: &Self::Input) -> Result<impl Display> {
+		let seed_to_location = almanac.seed_to_location()?;
+
+		almanac
+			.seed_nums
+			.iter()
+			.map(|num| seed_to_location.lookup(*num))
+			.min()
+			.ok_or_else(|| no_solution("there are no seeds"))
+	}
+
+	fn part2(almanac: &Self::Input) -> Result<impl Display> {
+		let seed_ranges = read_seed_ranges(&almanac.seed_nums)?;
+
+		almanac
+			.seed_to_location()?
+			.map_ranges(&seed_ranges)
+			.into_iter()
+			.filter(|range| range.start < range.end)
+			.map(|range| range.start)
+			.min()
+			.ok_or_else(|| no_solution("there are no seeds"))
+	}
+}
+
+fn read_seed_nums(header: &str) -> Result<Vec<u64>, Unexpected<'_>> {
+	let header = header.trim();
 	let seed_num_section = header
 		.strip_prefix("seeds: ")
-		.expect("Header should be of the form 'seeds: X Y Z'");
+		.ok_or_else(|| unexpected(header, "a header of the form 'seeds: X Y Z'"))?;
 
-	split_on_spaces_and_parse_nums(&seed_num_section)
+	split_on_spaces_and_parse_nums(seed_num_section)
 }
 
-fn read_seed_ranges(reader: &mut BufReader<Box<dyn Read>>) -> Vec<Range> {
-	read_seed_nums(reader)
+fn read_seed_ranges(seed_nums: &[u64]) -> Result<Vec<Range>> {
+	seed_nums
 		.chunks(2)
-		.map(|chunk| {
-			let start = *chunk.first().expect("Expected start of seed range");
-			let len = *chunk.last().expect("Expected end of seed range");
-			let end = start + len;
-			Range { start, end }
+		.map(|chunk| match chunk {
+			[start, len] => Ok(Range {
+				start: *start,
+				end: start.saturating_add(*len),
+			}),
+			_ => Err(no_solution("seed numbers should come in [start] [length] pairs")),
 		})
 		.collect()
 }
 
-fn read_seed_maps(reader: &mut BufReader<Box<dyn Read>>) -> HashMap<String, SeedMap> {
-	let mut buffer = String::new();
+fn read_seed_maps(
+	sections: &str,
+	validation: Validation,
+) -> Result<(Vec<SeedMap>, Vec<Unexpected<'_>>), Unexpected<'_>> {
+	let mut seed_maps = vec![];
+	let mut problems = vec![];
 
-	reader
-		.read_to_string(&mut buffer)
-		.expect("Input should have more content after header");
-
-	buffer
-		.split("\n\n")
-		.map(|section| {
-			let map = SeedMap::from(section.trim());
-			(map.dest.clone(), map)
-		})
-		.collect()
+	for section in sections.split("\n\n") {
+		let (map, found) = SeedMap::parse(section.trim(), validation)?;
+		seed_maps.push(map);
+		problems.extend(found);
+	}
+	Ok((seed_maps, problems))
 }
 
-pub fn part1(reader: &mut BufReader<Box<dyn Read>>) -> u64 {
-	let seed_nums = read_seed_nums(reader);
-	let seed_maps = read_seed_maps(reader);
-	seed_nums
-		.iter()
-		.map(|num| chained_lookup_part1(&seed_maps, *num))
-		.min()
-		.expect("Minimum value should exist")
+fn no_solution(reason: impl Into<String>) -> Error {
+	Error::NoSolution {
+		day: Day5::DAY,
+		reason: reason.into(),
+	}
 }
 
-pub fn part2(reader: &mut BufReader<Box<dyn Read>>) -> u64 {
-	let seed_ranges = read_seed_ranges(reader);
-	let seed_maps = read_seed_maps(reader)
-		.into_iter()
-		.map(|(_, map)| (map.source.clone(), map))
-		.collect();
-	let mut location_num: u64 = 0;
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solution::{DynSolution, Part};
 
-	loop {
-		let corresponding_seed_num = chained_lookup_part2(&seed_maps, location_num);
+	const EXAMPLE: &str = "\
+seeds: 79 14 55 13
 
-		if seed_ranges
-			.iter()
-			.any(|range| range.includes(corresponding_seed_num))
-		{
-			return location_num;
-		} else {
-			location_num += 1;
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+	#[test]
+	fn part1_example() {
+		assert_eq!(Day5.run(EXAMPLE, Part::One).unwrap().answer, "35");
+	}
+
+	#[test]
+	fn part2_example() {
+		assert_eq!(Day5.run(EXAMPLE, Part::Two).unwrap().answer, "46");
+	}
+
+	#[test]
+	fn allows_trailing_blank_lines() {
+		let input = format!("{}\n\n", EXAMPLE);
+		assert_eq!(Day5.run(&input, Part::One).unwrap().answer, "35");
+	}
+
+	#[test]
+	fn maps_ranges_by_splitting_at_entry_edges() {
+		let map = SeedMap::from("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
+		let mut mapped = map.map_ranges(&[Range { start: 40, end: 100 }, Range { start: 79, end: 93 }]);
+		mapped.sort_by_key(|range| range.start);
+
+		assert_eq!(
+			mapped,
+			[
+				Range { start: 40, end: 50 },
+				Range { start: 50, end: 52 },
+				Range { start: 52, end: 100 },
+				Range { start: 81, end: 95 },
+			]
+		);
+	}
+
+	#[test]
+	fn inverts_overlapping_destinations_like_reverse_lookup() {
+		let map = SeedMap::from("seed-to-soil map:\n100 10 10\n105 50 10").unwrap();
+		let inverse = map.invert();
+
+		assert_eq!(inverse.to_string(), "soil-to-seed map:\n10 100 10\n55 110 5");
+		for soil in 95..120 {
+			assert_eq!(inverse.lookup(soil), map.reverse_lookup(soil), "soil {}", soil);
 		}
 	}
-}
 
-fn chained_lookup_part1(seed_maps: &HashMap<String, SeedMap>, seed_num: u64) -> u64 {
-	let mut last_lookup_result = seed_num;
-	let targets = [
-		"soil",
-		"fertilizer",
-		"water",
-		"light",
-		"temperature",
-		"humidity",
-		"location",
-	];
+	#[test]
+	fn composed_map_agrees_with_each_map_in_turn() {
+		let almanac = Day5::parse(EXAMPLE).unwrap();
+		let composed = almanac.seed_to_location().unwrap();
+		let maps = almanac.maps_between("seed", "location").unwrap();
 
-	for target in targets {
-		last_lookup_result = seed_maps[target].lookup(last_lookup_result);
+		assert_eq!((composed.source.as_str(), composed.dest.as_str()), ("seed", "location"));
+		for seed in 0..200 {
+			let location = maps.iter().fold(seed, |num, map| map.lookup(num));
+			assert_eq!(composed.lookup(seed), location, "seed {}", seed);
+		}
 	}
-	last_lookup_result
-}
 
-fn chained_lookup_part2(seed_maps: &HashMap<String, SeedMap>, location_num: u64) -> u64 {
-	let mut last_lookup_result = location_num;
-	let targets = [
-		"seed",
-		"soil",
-		"fertilizer",
-		"water",
-		"light",
-		"temperature",
-		"humidity",
-	];
+	#[test]
+	fn inverts_and_prints_maps() {
+		let map = SeedMap::from("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
+		let inverse = map.invert();
+
+		assert_eq!(inverse.to_string(), "soil-to-seed map:\n98 50 2\n50 52 48");
+		for seed in [10, 50, 79, 99] {
+			assert_eq!(inverse.lookup(map.lookup(seed)), seed);
+			assert_eq!(inverse.lookup(seed), map.reverse_lookup(seed));
+		}
+		assert_eq!(SeedMap::from(&map.to_string()).unwrap().to_string(), map.to_string());
+	}
+
+	#[test]
+	fn maps_between_any_two_categories() {
+		let almanac = Day5::parse(EXAMPLE).unwrap();
+		let route = almanac.maps_between("water", "humidity").unwrap();
+		let water_to_humidity = almanac.map_between("water", "humidity").unwrap();
+
+		let dests = route.iter().map(|map| map.dest.as_str()).collect::<Vec<_>>();
+
+		assert_eq!(dests, ["light", "temperature", "humidity"]);
+		// Seed 79 has water 81, light 74, temperature 78 and humidity 78.
+		assert_eq!(water_to_humidity.lookup(81), 78);
+		assert_eq!(almanac.map_between("soil", "soil").unwrap().lookup(81), 81);
+	}
+
+	#[test]
+	fn reports_broken_category_graphs() {
+		let reason = |input: &str, from, to| match Day5::parse(input).unwrap().maps_between(from, to) {
+			Err(Error::NoSolution { reason, .. }) => reason,
+			other => panic!("expected no solution, got {:?}", other.map(|_| ())),
+		};
+
+		assert_eq!(
+			reason("seeds: 1\n\nseed-to-soil map:\n1 2 3", "seed", "location"),
+			"there is no chain of maps from 'seed' to 'location'"
+		);
+		assert_eq!(
+			reason("seeds: 1\n\nseed-to-soil map:\n1 2 3", "water", "soil"),
+			"there is no map from 'water'"
+		);
+		assert_eq!(
+			reason("seeds: 1\n\nseed-to-soil map:\n\nsoil-to-seed map:", "seed", "location"),
+			"the maps form a cycle: seed -> soil -> seed"
+		);
+		assert_eq!(
+			reason(
+				"seeds: 1\n\nseed-to-soil map:\n\nseed-to-water map:\n\nsoil-to-water map:",
+				"seed",
+				"water"
+			),
+			"there is more than one chain of maps from 'seed' to 'water': seed -> soil -> water and seed -> water"
+		);
+	}
+
+	#[test]
+	fn earlier_entries_win_where_sources_overlap() {
+		let section = "seed-to-soil map:\n100 10 10\n200 5 20";
+		let (map, problems) = SeedMap::parse(section, Validation::Lenient).unwrap();
+
+		assert_eq!(problems, [unexpected("200 5 20", "a source range that doesn't overlap '100 10 10'")]);
+		assert_eq!([4, 5, 12, 20, 24, 25].map(|seed| map.lookup(seed)), [4, 200, 102, 215, 219, 25]);
+	}
+
+	#[test]
+	fn strict_mode_rejects_suspicious_entries() {
+		let problem = |input: &str| match Almanac::parse(input, Validation::Strict) {
+			Err(Error::Parse { line, expected, .. }) => (line, expected),
+			other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+		};
+
+		assert_eq!(
+			problem("seeds: 1\n\nseed-to-soil map:\n50 98 2\n52 50 0"),
+			(5, String::from("an entry with a non-zero length"))
+		);
+		assert_eq!(
+			problem("seeds: 1\n\nseed-to-soil map:\n18446744073709551610 0 10"),
+			(4, String::from("an entry whose ranges end within u64"))
+		);
+		assert_eq!(
+			problem("seeds: 1\n\nseed-to-soil map:\n50 98 2\n51 0 5"),
+			(5, String::from("a destination range that doesn't overlap '50 98 2'"))
+		);
 
-	for target in targets.iter().rev() {
-		last_lookup_result = seed_maps[*target].reverse_lookup(last_lookup_result);
+		let lenient = Almanac::parse("seeds: 1\n\nseed-to-soil map:\n50 98 2\n51 0 0", Validation::Lenient);
+		let (_, problems) = lenient.unwrap();
+		assert_eq!(problems.len(), 1);
+		assert!(Almanac::parse(EXAMPLE, Validation::Strict).unwrap().1.is_empty());
 	}
-	last_lookup_result
 }
@@ -1,2 +1,2 @@
 pub mod day5;
-pub use day5::{part1, part2};
+pub use day5::{Almanac, Day5, SeedMap, Validation};
@@ -1,65 +1,179 @@
-fn strip_prefix_and_parse_nums(prefix: &str, line: Option<&String>) -> Vec<u64> {
-	line.and_then(|line| line.strip_prefix(prefix))
-		.map(|line| split_on_spaces_and_parse_nums(line.trim()))
-		.expect(&format!(
-			"Line should have prefix '{}' followed by whitespace-separated numbers",
-			prefix
-		))
-}
+use std::fmt::Display;
 
-fn split_on_spaces_and_parse_nums(s: &str) -> Vec<u64> {
-	s.split_whitespace()
-		.map(|number_str| number_str.parse())
-		.flatten()
-		.collect()
+use crate::{
+	error::{end_of, unexpected, Error, Result, Unexpected},
+	solution::Solution,
+	util::parse_num,
+};
+
+/// The race times and record distances, read both as separate races (part 1) and as a single race
+/// with the spaces between digits removed (part 2).
+#[derive(Debug)]
+pub struct RaceSheet {
+	times: Vec<u128>,
+	distances: Vec<u128>,
+	kerned_time: u128,
+	kerned_distance: u128,
 }
 
-fn strip_prefix_join_on_whitespace_and_parse_num(prefix: &str, s: Option<&String>) -> u64 {
-	s.and_then(|s| s.strip_prefix(prefix))
-		.and_then(|s| s.split_whitespace().collect::<Vec<_>>().join("").parse().ok())
-		.expect("Joined line should be numeric")
+fn strip_prefix<'a>(prefix: &str, line: &'a str) -> Result<&'a str, Unexpected<'a>> {
+	line.strip_prefix(prefix).ok_or_else(|| {
+		unexpected(
+			line,
+			format!("a line starting with '{}' followed by whitespace-separated numbers", prefix),
+		)
+	})
 }
 
-fn lower_bound(distance: u64, time: u64) -> f64 {
-	((time as f64) - f64::sqrt((time * time) as f64 - 4. * (distance as f64))) / 2.
+fn split_on_spaces_and_parse_nums(s: &str) -> Result<Vec<u128>, Unexpected<'_>> {
+	s.split_whitespace().map(parse_num).collect()
 }
 
-fn upper_bound(distance: u64, time: u64) -> f64 {
-	((time as f64) + f64::sqrt((time * time) as f64 - 4. * (distance as f64))) / 2.
+fn join_on_whitespace_and_parse_num(s: &str) -> Result<u128, Unexpected<'_>> {
+	s.split_whitespace()
+		.collect::<Vec<_>>()
+		.join("")
+		.parse()
+		.map_err(|_| unexpected(s.trim(), "digits forming a single number once spaces are removed"))
 }
 
-fn clamp(min: f64, max: f64) -> (u64, u64) {
-	if max.fract() == 0. {
-		(min.ceil() as u64 + 1, max as u64 - 1)
-	} else {
-		(min.ceil() as u64, max as u64)
+fn read_race_sheet(input: &str) -> Result<RaceSheet, Unexpected<'_>> {
+	let mut lines = input.lines();
+	let (first, second) = (lines.next().unwrap_or(input), lines.next().unwrap_or(end_of(input)));
+	let time_line = strip_prefix("Time:", first)?;
+	let distance_line = strip_prefix("Distance:", second)?;
+
+	let times = split_on_spaces_and_parse_nums(time_line)?;
+	let distances = split_on_spaces_and_parse_nums(distance_line)?;
+	if times.len() != distances.len() {
+		let (shorter, expected) = if times.len() < distances.len() {
+			(first, distances.len())
+		} else {
+			(second, times.len())
+		};
+		return Err(unexpected(shorter, format!("{} numbers, one for each race", expected)));
 	}
+
+	Ok(RaceSheet {
+		times,
+		distances,
+		kerned_time: join_on_whitespace_and_parse_num(time_line)?,
+		kerned_distance: join_on_whitespace_and_parse_num(distance_line)?,
+	})
 }
 
-fn get_clamped_bounds(distance: u64, time: u64) -> (u64, u64) {
-	let lower_root = lower_bound(distance, time);
-	let upper_root = upper_bound(distance, time);
-	clamp(lower_root, upper_root)
+/// Whether holding the button for `hold` out of `time` goes further than `distance`. A distance
+/// too big for `u128` beats any record.
+fn beats(hold: u128, time: u128, distance: u128) -> bool {
+	hold.checked_mul(time - hold).is_none_or(|travelled| travelled > distance)
 }
 
-pub fn part1(lines: Vec<String>) -> u64 {
-	let times = strip_prefix_and_parse_nums("Time:", lines.first());
-	let distances = strip_prefix_and_parse_nums("Distance:", lines.last());
-	let races = times.iter().zip(distances);
-	let mut product = 1;
+/// The number of whole hold times that beat the record `distance` in a race lasting `time`.
+///
+/// The distance travelled rises up to a hold of `time / 2` and falls symmetrically after, so this
+/// binary searches the rising half for the shortest winning hold; everything between it and its
+/// mirror image wins too. This stays exact where the square roots in the closed-form bounds don't.
+pub fn ways_to_win(time: u128, distance: u128) -> u128 {
+	let best = time / 2;
+	if !beats(best, time, distance) {
+		return 0;
+	}
 
-	for (duration, distance_record) in races {
-		let (lower, upper) = get_clamped_bounds(distance_record, *duration);
-		let num_ways = upper - lower + 1;
-		product *= num_ways;
+	// Holding for `high` always wins, and everything below `low` loses.
+	let (mut low, mut high) = (0, best);
+	while low < high {
+		let mid = low + (high - low) / 2;
+		if beats(mid, time, distance) {
+			high = mid;
+		} else {
+			low = mid + 1;
+		}
 	}
-	product
+
+	time - 2 * high + 1
 }
 
-pub fn part2(lines: Vec<String>) -> u64 {
-	let time = strip_prefix_join_on_whitespace_and_parse_num("Time:", lines.first());
-	let distance = strip_prefix_join_on_whitespace_and_parse_num("Distance:", lines.last());
-	let (lower, upper) = get_clamped_bounds(distance, time);
-	let num_ways = upper - lower + 1;
-	num_ways
+pub struct Day6;
+
+impl Solution for Day6 {
+	const DAY: u8 = 6;
+
+	type Input = RaceSheet;
+
+	fn parse(input: &str) -> Result<Self::Input> {
+		read_race_sheet(input).map_err(|err| err.locate(Self::DAY, input))
+	}
+
+	fn part1(sheet: &Self::Input) -> Result<impl Display> {
+		let races = sheet.times.iter().zip(&sheet.distances);
+		let mut product: u128 = 1;
+
+		for (duration, distance_record) in races {
+			product = product
+				.checked_mul(ways_to_win(*duration, *distance_record))
+				.ok_or_else(|| Error::NoSolution {
+					day: Self::DAY,
+					reason: String::from("the number of ways to win overflows u128"),
+				})?;
+		}
+		Ok(product)
+	}
+
+	fn part2(sheet: &Self::Input) -> Result<impl Display> {
+		Ok(ways_to_win(sheet.kerned_time, sheet.kerned_distance))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solution::{DynSolution, Part};
+
+	const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+	#[test]
+	fn part1_example() {
+		assert_eq!(Day6.run(EXAMPLE, Part::One).unwrap().answer, "288");
+	}
+
+	#[test]
+	fn part2_example() {
+		assert_eq!(Day6.run(EXAMPLE, Part::Two).unwrap().answer, "71503");
+	}
+
+	#[test]
+	fn reports_races_without_a_record() {
+		let err = Day6::parse("Time: 7 15\nDistance: 9\n").unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"day 6, line 2, column 1: expected 2 numbers, one for each race, found 'Distance: 9'"
+		);
+	}
+
+	#[test]
+	fn counts_edge_cases_exactly() {
+		assert_eq!(ways_to_win(0, 0), 0);
+		assert_eq!(ways_to_win(1, 0), 0);
+		assert_eq!(ways_to_win(2, 0), 1);
+		// Holding for 5 only matches the record, so nothing wins.
+		assert_eq!(ways_to_win(10, 25), 0);
+		assert_eq!(ways_to_win(10, 24), 1);
+		assert_eq!(ways_to_win(11, 29), 2);
+		assert_eq!(ways_to_win(11, 30), 0);
+	}
+
+	#[test]
+	fn counts_races_beyond_floating_point_precision() {
+		let time: u128 = (1 << 64) + 3;
+		let best = (time / 2) * (time - time / 2);
+
+		assert_eq!(ways_to_win(time, best), 0);
+		assert_eq!(ways_to_win(time, best - 1), 2);
+		assert_eq!(ways_to_win(time, 0), time - 1);
+		// Holding for 1 only matches the record; holding for 2 goes further than fits in a u128.
+		assert_eq!(ways_to_win(u128::MAX, u128::MAX - 1), u128::MAX - 3);
+	}
 }
@@ -1,2 +1,2 @@
 pub mod day6;
-pub use day6::{part1,part2};
+pub use day6::Day6;
@@ -1,5 +1,11 @@
 use once_cell::sync::Lazy;
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, fmt::Display};
+
+use crate::{
+    error::{unexpected, Result, Unexpected},
+    solution::Solution,
+    util::parse_num,
+};
 
 type Cards = [char; 5];
 static CARD_VALUES: Lazy<HashMap<char, u32>> =
@@ -8,8 +14,6 @@ static CARD_VALUES: Lazy<HashMap<char, u32>> =
 static CARD_VALUES_PART2: Lazy<HashMap<char, u32>> =
     Lazy::new(|| HashMap::from([('A', 13), ('K', 12), ('Q', 11), ('T', 10), ('J', 1)]));
 
-static PART2: bool = true;
-
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 enum HandType {
     HighCard,
@@ -21,16 +25,19 @@ enum HandType {
     FiveOfKind,
 }
 
-#[derive(Debug)]
-struct Hand {
+#[derive(Debug, Clone)]
+pub struct Hand {
     hand_type: HandType,
     cards: Cards,
     bid: u32,
+    jokers: bool,
 }
 
 impl HandType {
-    pub fn from(s: &str) -> Self {
-        if PART2 {
+    pub fn from(cards: &Cards, jokers: bool) -> Self {
+        let s = &String::from_iter(cards);
+
+        if jokers {
             let labels = CARD_VALUES_PART2
                 .clone()
                 .into_keys()
@@ -73,27 +80,40 @@ impl HandType {
 }
 
 impl Hand {
-    pub fn from(s: String) -> Self {
-        let (hand_type, cards, bid) = s
-            .split_once(" ")
-            .map(|(content, bid)| {
-                let hand_type = HandType::from(content);
-                let bid = bid.parse().expect("Bid value should be numeric");
-                let cards = content
-                    .chars()
-                    .collect::<Vec<_>>()
-                    .as_slice()
-                    .try_into()
-                    .expect("Content should consist of 5 chars");
-
-                (hand_type, cards, bid)
-            })
-            .expect("Input line should be of the form [hand] [bid]");
+    pub fn from(s: &str) -> Result<Self, Unexpected<'_>> {
+        let (content, bid) = s
+            .split_once(' ')
+            .ok_or_else(|| unexpected(s, "a line of the form [hand] [bid]"))?;
 
-        Self {
-            hand_type,
+        if let Some((i, c)) = content.char_indices().find(|(_, c)| !is_card_label(*c)) {
+            return Err(unexpected(
+                &content[i..i + c.len_utf8()],
+                "a card label (A, K, Q, J, T or 2-9)",
+            ));
+        }
+
+        let cards: Cards = content
+            .chars()
+            .collect::<Vec<_>>()
+            .as_slice()
+            .try_into()
+            .map_err(|_| unexpected(content, "a hand of 5 cards"))?;
+        let bid = parse_num(bid)?;
+
+        Ok(Self {
+            hand_type: HandType::from(&cards, false),
             bid,
             cards,
+            jokers: false,
+        })
+    }
+
+    /// The same hand, re-scored with 'J' treated as a joker rather than a jack.
+    pub fn with_jokers(&self) -> Self {
+        Self {
+            hand_type: HandType::from(&self.cards, true),
+            jokers: true,
+            ..*self
         }
     }
 }
@@ -115,8 +135,8 @@ impl Ord for Hand {
                 (0..hand_size)
                     .find(|pos| self.cards[*pos] != other.cards[*pos])
                     .map(|differing_position| {
-                        get_card_value(self.cards[differing_position])
-                            .cmp(&get_card_value(other.cards[differing_position]))
+                        get_card_value(self.cards[differing_position], self.jokers)
+                            .cmp(&get_card_value(other.cards[differing_position], other.jokers))
                     })
                     .unwrap_or(Ordering::Equal)
             } else {
@@ -129,8 +149,8 @@ impl Ord for Hand {
 impl PartialEq for Hand {
     fn eq(&self, other: &Self) -> bool {
         if self.hand_type == other.hand_type {
-            let mut own = self.cards.clone();
-            let mut others = other.cards.clone();
+            let mut own = self.cards;
+            let mut others = other.cards;
             own.sort();
             others.sort();
             own == others
@@ -170,11 +190,15 @@ fn determine_hand_type(max_duplicates: usize, num_unique_cards: usize) -> HandTy
     }
 }
 
-fn get_card_value(card: char) -> u32 {
+fn is_card_label(card: char) -> bool {
+    ('2'..='9').contains(&card) || CARD_VALUES.contains_key(&card)
+}
+
+fn get_card_value(card: char, jokers: bool) -> u32 {
     if let Some(num) = card.to_digit(10) {
         num
     } else {
-        let val = if PART2 {
+        let val = if jokers {
             &CARD_VALUES_PART2
         } else {
             &CARD_VALUES
@@ -186,23 +210,76 @@ fn get_card_value(card: char) -> u32 {
     }
 }
 
-pub fn part1(lines: impl Iterator<Item = String>) -> u64 {
-    let mut hands = lines.map(Hand::from).collect::<Vec<_>>();
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Hand>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| Hand::from(line).map_err(|err| err.locate(Self::DAY, input)))
+            .collect()
+    }
+
+    fn part1(hands: &Self::Input) -> Result<impl Display> {
+        Ok(total_winnings(hands.clone()))
+    }
+
+    fn part2(hands: &Self::Input) -> Result<impl Display> {
+        Ok(total_winnings(hands.iter().map(Hand::with_jokers).collect()))
+    }
+}
+
+fn total_winnings(mut hands: Vec<Hand>) -> u64 {
     hands.sort();
 
     let mut total = 0;
 
     for (i, hand) in hands.iter().enumerate() {
         let rank = (i + 1) as u32;
-        println!("{} - {:?}", rank, hand);
         total += (rank * hand.bid) as u64
     }
     total
 }
 
-pub fn part2(lines: impl Iterator<Item = String>) -> u64 {
-    if !PART2 {
-        panic!("Invoked part2 without setting flag");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day7.run(EXAMPLE, Part::One).unwrap().answer, "6440");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day7.run(EXAMPLE, Part::Two).unwrap().answer, "5905");
+    }
+
+    #[test]
+    fn reports_hands_of_the_wrong_size() {
+        let err = Day7::parse("32T3K 765\nAAAAAA 1\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 7, line 2, column 1: expected a hand of 5 cards, found 'AAAAAA'"
+        );
+
+        let err = Day7::parse(" 5\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 7, line 1, column 1: expected a hand of 5 cards, found end of line"
+        );
     }
-    part1(lines)
 }
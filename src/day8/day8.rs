@@ -1,8 +1,10 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::{
-    collections::HashMap,
-    io::{BufRead, BufReader, Read},
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    error::{unexpected, Error, Result, Unexpected},
+    solution::Solution,
 };
 
 type Node = (String, String, String);
@@ -14,9 +16,9 @@ static NODE_REGEX: Lazy<Regex> =
 fn solve(
     start_pos: &str,
     map: &Map,
-    directions: &Vec<char>,
+    directions: &[char],
     stop_cond: impl Fn(&String) -> bool,
-) -> u64 {
+) -> Result<u64> {
     let mut current_pos = &String::from(start_pos);
     let mut num_moves = 0;
 
@@ -25,70 +27,84 @@ fn solve(
             break;
         }
 
-        let (left, right) = &map[current_pos];
+        let (left, right) = map.get(current_pos).ok_or_else(|| Error::NoSolution {
+            day: Day8::DAY,
+            reason: format!("node '{}' isn't in the map", current_pos),
+        })?;
         current_pos = match dir {
             'L' => left,
             'R' => right,
-            _ => panic!("Directions should consist of only L/R"),
+            _ => unreachable!("directions are checked to be L/R when parsing"),
         };
         num_moves += 1;
     }
 
-    num_moves
+    Ok(num_moves)
 }
 
-pub fn part1(reader: &mut BufReader<Box<dyn Read>>) -> u64 {
-    let directions = read_directions(reader);
-    let map = read_nodes(reader);
-    solve("AAA", &map, &directions, |pos| pos == "ZZZ")
-}
+pub struct Day8;
 
-pub fn part2(reader: &mut BufReader<Box<dyn Read>>) -> u64 {
-    let directions = read_directions(reader);
-    let map = read_nodes(reader);
-    let current_positions = map.keys().filter(|node| node.ends_with("A"));
+impl Solution for Day8 {
+    const DAY: u8 = 8;
 
-    current_positions
-        .map(|pos| solve(pos, &map, &directions, |pos| pos.ends_with("Z")))
-        .reduce(lcm)
-        .expect("Should be able to reduce the step count array to its least-common-multiple")
-}
+    type Input = (Vec<char>, Map);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = input.lines();
+        let parsed = read_directions(lines.next().unwrap_or(input))
+            .and_then(|directions| Ok((directions, read_nodes(lines.skip(1))?)));
+
+        parsed.map_err(|err| err.locate(Self::DAY, input))
+    }
+
+    fn part1((directions, map): &Self::Input) -> Result<impl Display> {
+        solve("AAA", map, directions, |pos| pos == "ZZZ")
+    }
 
-fn read_directions(reader: &mut BufReader<Box<dyn Read>>) -> Vec<char> {
-    let mut buf = String::new();
-    reader
-        .read_line(&mut buf)
-        .expect("Input should begin with a line of directions");
+    fn part2((directions, map): &Self::Input) -> Result<impl Display> {
+        let current_positions = map.keys().filter(|node| node.ends_with("A"));
+
+        current_positions
+            .map(|pos| solve(pos, map, directions, |pos| pos.ends_with("Z")))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .reduce(lcm)
+            .ok_or_else(|| Error::NoSolution {
+                day: Self::DAY,
+                reason: String::from("there are no starting nodes ending in 'A'"),
+            })
+    }
+}
 
-    let _ = reader.read_line(&mut buf);
-    buf.trim().chars().collect()
+fn read_directions(line: &str) -> Result<Vec<char>, Unexpected<'_>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Err(unexpected(line, "a direction (L or R)"));
+    }
+    match line.char_indices().find(|(_, c)| !matches!(c, 'L' | 'R')) {
+        Some((i, c)) => Err(unexpected(&line[i..i + c.len_utf8()], "a direction (L or R)")),
+        None => Ok(line.chars().collect()),
+    }
 }
 
-fn read_nodes(reader: &mut BufReader<Box<dyn Read>>) -> HashMap<String, (String, String)> {
-    reader
-        .lines()
-        .flat_map(Result::ok)
+fn read_nodes<'a>(
+    lines: impl Iterator<Item = &'a str>,
+) -> Result<Map, Unexpected<'a>> {
+    lines
         .map(|line| {
-            let (pos, left, right) = read_node(&line);
-            (pos, (left, right))
+            let (pos, left, right) = read_node(line)?;
+            Ok((pos, (left, right)))
         })
         .collect()
 }
 
-fn read_node(line: &String) -> Node {
-    let [pos, left, right]: [String; 3] = NODE_REGEX
-        .captures(&line)
-        .expect("Line should be of the form (ABC) = (DEF, XYZ)")
-        .iter()
-        .skip(1)
-        .take(3)
-        .flatten()
-        .map(|m| String::from(m.as_str()))
-        .collect::<Vec<_>>()
-        .try_into()
-        .expect("Failed to extract regex capture groups");
-
-    (pos, left, right)
+fn read_node(line: &str) -> Result<Node, Unexpected<'_>> {
+    let (_, [pos, left, right]) = NODE_REGEX
+        .captures(line)
+        .ok_or_else(|| unexpected(line, "a line of the form ABC = (DEF, XYZ)"))?
+        .extract();
+
+    Ok((String::from(pos), String::from(left), String::from(right)))
 }
 
 fn gcd(a: u64, b: u64) -> u64 {
@@ -102,3 +118,49 @@ fn gcd(a: u64, b: u64) -> u64 {
 fn lcm(a: u64, b: u64) -> u64 {
     a * (b / gcd(a, b))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    const EXAMPLE_PART1: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE_PART2: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day8.run(EXAMPLE_PART1, Part::One).unwrap().answer, "6");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day8.run(EXAMPLE_PART2, Part::Two).unwrap().answer, "6");
+    }
+
+    #[test]
+    fn rejects_missing_directions() {
+        let err = Day8::parse("\n\nAAA = (ZZZ, ZZZ)\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 8, line 1, column 1: expected a direction (L or R), found end of line"
+        );
+    }
+}
@@ -1,2 +1,2 @@
 pub mod day8;
-pub use day8::{part1, part2};
+pub use day8::Day8;
@@ -1,20 +1,42 @@
-pub fn part1(lines: impl Iterator<Item = String>) -> i64 {
-    lines
-        .map(|line| next(split_on_spaces_and_parse_nums(&line)))
-        .sum()
-}
+use std::fmt::Display;
 
-pub fn part2(lines: impl Iterator<Item = String>) -> i64 {
-    lines
-        .map(|line| {
-            let nums_reversed = split_on_spaces_and_parse_nums(&line)
-                .into_iter()
-                .rev()
-                .collect();
+use crate::{
+    error::{Result, Unexpected},
+    solution::Solution,
+    util::parse_num,
+};
 
-            next(nums_reversed)
-        })
-        .sum()
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| split_on_spaces_and_parse_nums(line).map_err(|err| err.locate(Self::DAY, input)))
+            .collect()
+    }
+
+    fn part1(sequences: &Self::Input) -> Result<impl Display> {
+        Ok(sequences
+            .iter()
+            .map(|nums| next(nums.clone()))
+            .sum::<i64>())
+    }
+
+    fn part2(sequences: &Self::Input) -> Result<impl Display> {
+        Ok(sequences
+            .iter()
+            .map(|nums| {
+                let nums_reversed = nums.iter().rev().copied().collect();
+
+                next(nums_reversed)
+            })
+            .sum::<i64>())
+    }
 }
 
 fn next(values: Vec<i64>) -> i64 {
@@ -24,7 +46,7 @@ fn next(values: Vec<i64>) -> i64 {
         .enumerate()
         .map(|(k, val)| {
             // https://amsi.org.au/ESA_Senior_Years/SeniorTopic1/1c/1c_2content_4 (Observation 4)
-            let sign: i64 = (-1i64).pow((n - (k as i64) + 1) as u32) as i64;
+            let sign: i64 = (-1i64).pow((n - (k as i64) + 1) as u32);
             sign * val * n_choose_k(n, k as i64)
         })
         .sum()
@@ -38,9 +60,28 @@ fn n_choose_k(n: i64, k: i64) -> i64 {
     result
 }
 
-fn split_on_spaces_and_parse_nums(s: &str) -> Vec<i64> {
-    s.split_whitespace()
-        .map(|number_str| number_str.parse())
-        .flatten()
-        .collect()
+fn split_on_spaces_and_parse_nums(s: &str) -> Result<Vec<i64>, Unexpected<'_>> {
+    s.split_whitespace().map(parse_num).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{DynSolution, Part};
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day9.run(EXAMPLE, Part::One).unwrap().answer, "114");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day9.run(EXAMPLE, Part::Two).unwrap().answer, "2");
+    }
 }
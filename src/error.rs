@@ -0,0 +1,94 @@
+use std::fmt;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The puzzle input didn't match the expected format. Lines and columns are 1-based.
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        expected: String,
+        found: String,
+    },
+    /// The input parsed, but doesn't admit an answer.
+    NoSolution { day: u8, reason: String },
+}
+
+/// A parse failure that points at the offending slice of the input, but hasn't yet been located
+/// within the input as a whole. Parsers return this so that they don't need to track line numbers
+/// themselves; `locate` recovers the position from where `found` sits in memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unexpected<'a> {
+    pub found: &'a str,
+    pub expected: String,
+}
+
+pub fn unexpected<'a>(found: &'a str, expected: impl Into<String>) -> Unexpected<'a> {
+    Unexpected {
+        found,
+        expected: expected.into(),
+    }
+}
+
+/// The empty slice just past the end of `s`, for reporting input that stopped short.
+pub fn end_of(s: &str) -> &str {
+    &s[s.len()..]
+}
+
+impl Unexpected<'_> {
+    /// Converts to an `Error`, given the full input that `found` was sliced from.
+    pub fn locate(self, day: u8, input: &str) -> Error {
+        let offset = (self.found.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len());
+
+        let (line, column) = match offset {
+            Some(offset) => {
+                let before = &input[..offset];
+                let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                (
+                    before.matches('\n').count() + 1,
+                    before[line_start..].chars().count() + 1,
+                )
+            }
+            None => (0, 0),
+        };
+
+        Error::Parse {
+            day,
+            line,
+            column,
+            expected: self.expected,
+            found: self.found.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                day,
+                line,
+                column,
+                expected,
+                found,
+            } => {
+                write!(f, "day {}", day)?;
+                if *line > 0 {
+                    write!(f, ", line {}, column {}", line, column)?;
+                }
+                if found.is_empty() {
+                    write!(f, ": expected {}, found end of line", expected)
+                } else {
+                    write!(f, ": expected {}, found '{}'", expected, found)
+                }
+            }
+            Error::NoSolution { day, reason } => write!(f, "day {}: no solution: {}", day, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
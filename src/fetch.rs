@@ -0,0 +1,296 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    input::{normalize_line_endings, InputSource},
+    util::INPUT_DIR,
+};
+
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub(crate) const USER_AGENT: &str = "github.com/sploicers/aoc-2023";
+pub(crate) const YEAR: u16 = 2023;
+
+/// Minimum time between two fetches, across every day and every run.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Name of the file in the cache directory recording every fetch attempt.
+const FETCH_LOG: &str = ".fetch-log";
+
+#[derive(Debug)]
+pub enum FetchError {
+    Io(io::Error),
+    MissingSession,
+    Http(String),
+    /// A day's input was already downloaded once; fetching it again would only hit the server
+    /// because the cached copy went missing.
+    AlreadyFetched { day: u8 },
+    RateLimited { retry_after: Duration },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Io(err) => write!(f, "{}", err),
+            FetchError::MissingSession => {
+                write!(f, "set {} to your adventofcode.com session cookie", SESSION_ENV_VAR)
+            }
+            FetchError::Http(reason) => write!(f, "request failed: {}", reason),
+            FetchError::AlreadyFetched { day } => write!(
+                f,
+                "day {} was already fetched once; remove it from the fetch log to fetch it again",
+                day
+            ),
+            FetchError::RateLimited { retry_after } => {
+                write!(f, "fetched too recently, try again in {}s", retry_after.as_secs() + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(err: io::Error) -> Self {
+        FetchError::Io(err)
+    }
+}
+
+/// Something that can download a day's puzzle input.
+pub trait Fetcher {
+    fn fetch(&self, day: u8) -> Result<String, FetchError>;
+}
+
+/// Downloads inputs over HTTP, authenticating with the session cookie.
+pub struct HttpFetcher {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Talks to adventofcode.com, with the session read from `AOC_SESSION` if it's set. A missing
+    /// session is only an error once something actually needs fetching.
+    pub fn from_env() -> Self {
+        Self::new(BASE_URL, env::var(SESSION_ENV_VAR).ok())
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, day: u8) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+
+        self.agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .call()
+            .map_err(|err| FetchError::Http(err.to_string()))?
+            .into_string()
+            .map_err(FetchError::Io)
+    }
+}
+
+/// Finds puzzle inputs on disk, downloading and storing any that are missing. Every download is
+/// recorded in a log alongside the inputs, which is used to wait at least `min_interval` between
+/// downloads and to never download the same day twice.
+pub struct InputCache<F: Fetcher> {
+    dir: PathBuf,
+    fetcher: F,
+    min_interval: Duration,
+}
+
+impl InputCache<HttpFetcher> {
+    /// The `input/` directory, backed by adventofcode.com.
+    pub fn from_env() -> Self {
+        Self::new(INPUT_DIR, HttpFetcher::from_env())
+    }
+}
+
+impl<F: Fetcher> InputCache<F> {
+    pub fn new(dir: impl Into<PathBuf>, fetcher: F) -> Self {
+        Self {
+            dir: dir.into(),
+            fetcher,
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    pub fn with_min_interval(self, min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            ..self
+        }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}.txt", day))
+    }
+
+    pub fn get(&self, day: u8) -> Result<String, FetchError> {
+        let path = self.path(day);
+        if path.exists() {
+            return Ok(InputSource::Path(path).read()?);
+        }
+
+        let log = self.read_log()?;
+        if log.iter().any(|entry| entry.day == day && entry.succeeded) {
+            return Err(FetchError::AlreadyFetched { day });
+        }
+
+        let now = unix_time();
+        if let Some(last) = log.iter().map(|entry| entry.time).max() {
+            let elapsed = now.saturating_sub(last);
+            if elapsed < self.min_interval {
+                return Err(FetchError::RateLimited {
+                    retry_after: self.min_interval - elapsed,
+                });
+            }
+        }
+
+        let fetched = self.fetcher.fetch(day);
+        if !matches!(fetched, Err(FetchError::MissingSession)) {
+            self.append_to_log(&LogEntry {
+                day,
+                time: now,
+                succeeded: fetched.is_ok(),
+            })?;
+        }
+
+        let input = normalize_line_endings(&fetched?);
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    fn read_log(&self) -> io::Result<Vec<LogEntry>> {
+        match fs::read_to_string(self.dir.join(FETCH_LOG)) {
+            Ok(log) => Ok(log.lines().filter_map(LogEntry::from).collect()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn append_to_log(&self, entry: &LogEntry) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(FETCH_LOG))?;
+
+        writeln!(
+            log,
+            "{} {} {}",
+            entry.day,
+            entry.time.as_secs(),
+            if entry.succeeded { "ok" } else { "failed" }
+        )
+    }
+}
+
+/// One line of the fetch log: `[day] [unix seconds] [ok|failed]`.
+struct LogEntry {
+    day: u8,
+    time: Duration,
+    succeeded: bool,
+}
+
+impl LogEntry {
+    fn from(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        Some(Self {
+            day: fields.next()?.parse().ok()?,
+            time: Duration::from_secs(fields.next()?.parse().ok()?),
+            succeeded: fields.next()? == "ok",
+        })
+    }
+}
+
+pub(crate) fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-cache-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn serve_inputs() -> MockServer {
+        MockServer::start(|request| {
+            if request.contains("Cookie: session=secret") {
+                (200, String::from("1abc2\r\npqr3stu8vwx\r\n"))
+            } else {
+                (400, String::from("Puzzle inputs differ by user."))
+            }
+        })
+    }
+
+    #[test]
+    fn fetches_missing_input_and_stores_it() {
+        let server = serve_inputs();
+        let dir = cache_dir("fetch");
+        let cache = InputCache::new(&dir, HttpFetcher::new(&server.url, Some(String::from("secret"))));
+
+        assert_eq!(cache.get(1).unwrap(), "1abc2\npqr3stu8vwx\n");
+        assert_eq!(fs::read_to_string(dir.join("day1.txt")).unwrap(), "1abc2\npqr3stu8vwx\n");
+        assert!(server.requests()[0].starts_with("GET /2023/day/1/input "));
+
+        // Served from disk from now on.
+        assert_eq!(cache.get(1).unwrap(), "1abc2\npqr3stu8vwx\n");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn never_fetches_the_same_day_twice() {
+        let server = serve_inputs();
+        let dir = cache_dir("twice");
+        let cache = InputCache::new(&dir, HttpFetcher::new(&server.url, Some(String::from("secret"))))
+            .with_min_interval(Duration::ZERO);
+
+        cache.get(1).unwrap();
+        fs::remove_file(cache.path(1)).unwrap();
+
+        assert!(matches!(cache.get(1), Err(FetchError::AlreadyFetched { day: 1 })));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn waits_between_fetches() {
+        let server = serve_inputs();
+        let dir = cache_dir("rate-limit");
+        let cache = InputCache::new(&dir, HttpFetcher::new(&server.url, Some(String::from("secret"))));
+
+        cache.get(1).unwrap();
+
+        assert!(matches!(cache.get(2), Err(FetchError::RateLimited { .. })));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn requires_a_session_to_fetch() {
+        let server = serve_inputs();
+        let cache = InputCache::new(cache_dir("no-session"), HttpFetcher::new(&server.url, None));
+
+        assert!(matches!(cache.get(1), Err(FetchError::MissingSession)));
+        assert!(server.requests().is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use flate2::read::GzDecoder;
+use std::{
+    convert::Infallible,
+    fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::util::default_input_path;
+
+/// The first two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Where a puzzle input is read from. Gzip-compressed files and stdin are detected by their magic
+/// bytes, so `day5.txt.gz` works anywhere `day5.txt` does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// The conventional `input/dayN.txt` location.
+    pub fn default_for(day: u8) -> Self {
+        Self::Path(default_input_path(day))
+    }
+
+    /// Reads the whole input, decompressing it if need be and converting CRLF line endings to LF
+    /// so that solutions only ever have to deal with `\n`.
+    pub fn read(&self) -> io::Result<String> {
+        let bytes = match self {
+            Self::Path(path) => fs::read(path)?,
+            Self::Stdin => {
+                let mut bytes = vec![];
+                io::stdin().read_to_end(&mut bytes)?;
+                bytes
+            }
+            Self::Inline(text) => return Ok(normalize_line_endings(text)),
+        };
+
+        let text = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut text = String::new();
+            GzDecoder::new(bytes.as_slice()).read_to_string(&mut text)?;
+            text
+        } else {
+            String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        };
+
+        Ok(normalize_line_endings(&text))
+    }
+}
+
+/// Parses a command-line argument, with `-` standing in for stdin.
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => Self::Stdin,
+            path => Self::Path(PathBuf::from(path)),
+        })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+            Self::Inline(_) => write!(f, "inline input"),
+        }
+    }
+}
+
+pub fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::{env, io::Write};
+
+    #[test]
+    fn inline_input_has_crlf_normalized() {
+        let source = InputSource::Inline(String::from("a\r\nb\r\n\r\nc"));
+        assert_eq!(source.read().unwrap(), "a\nb\n\nc");
+    }
+
+    #[test]
+    fn gzip_file_is_decompressed() {
+        let path = env::temp_dir().join(format!("aoc-input-{}.txt.gz", std::process::id()));
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(b"seeds: 79 14\r\n").unwrap();
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let text = InputSource::Path(path.clone()).read();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(text.unwrap(), "seeds: 79 14\n");
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let source: InputSource = "input/no-such-day.txt".parse().unwrap();
+        assert_eq!(source.read().unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
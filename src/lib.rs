@@ -0,0 +1,32 @@
+//! Advent of Code 2023 solutions. Each `dayN` module exposes its `Solution` implementation along
+//! with the data structures it parses its input into; `solution::REGISTRY` lists every day.
+
+#![allow(clippy::module_inception)]
+#[macro_use]
+extern crate lazy_static;
+
+pub mod day1;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day17;
+pub mod day19;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod answers;
+pub mod error;
+pub mod fetch;
+pub mod input;
+pub mod runner;
+pub mod solution;
+pub mod submit;
+pub mod util;
+
+#[cfg(test)]
+mod mock_server;
@@ -1,23 +1,428 @@
-#![allow(dead_code)]
-#[macro_use]
-extern crate lazy_static;
-
-use util::get_input_reader;
-mod day1;
-mod day11;
-mod day12;
-mod day13;
-mod day19;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod util;
-
-fn main() {
-    println!("{}", day13::part2(&mut get_input_reader()));
+use advent_of_code_2023::{
+    answers::{Answers, DEFAULT_ANSWERS_PATH},
+    day1::{explain::explain, vocabulary::Vocabulary},
+    day2::{
+        day2::{cubes, Cubes, PART1_LIMITS},
+        query, Day2,
+    },
+    day3::{
+        gears::GearQuery,
+        render::{render_ansi, render_html},
+        Day3,
+    },
+    day5::{Almanac, Validation},
+    fetch::InputCache,
+    input::InputSource,
+    runner,
+    solution::{self, Part, Solution},
+    submit::{Outcome, SubmissionClient},
+};
+use clap::{Parser, Subcommand};
+use std::{fs, path::PathBuf, process::ExitCode};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single part of a single day's solution.
+    Run {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Path to the puzzle input (optionally gzipped), or `-` for stdin. Defaults to
+        /// `input/dayN.txt`, which is downloaded using `AOC_SESSION` if it doesn't exist yet.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+    },
+
+    /// Run a single part of a single day's solution and submit the answer, unless earlier
+    /// submissions show it can't be right.
+    Submit {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Path to the puzzle input, as for `run`.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+    },
+
+    /// Show how each line of a day 1 calibration document is read: every token found, the first
+    /// and last digits picked, and the resulting value.
+    ExplainDay1 {
+        /// Which part's vocabulary to use: literal digits only, or digits and English words.
+        #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Path to the puzzle input, as for `run`.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+
+        /// File of `[token] [digit]` lines to use instead of the part's vocabulary.
+        #[arg(long)]
+        vocabulary: Option<PathBuf>,
+    },
+
+    /// Ask questions about the games in a day 2 input.
+    QueryDay2 {
+        /// Path to the puzzle input, as for `run`.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+
+        #[command(subcommand)]
+        query: Day2Query,
+    },
+
+    /// Draw a day 3 schematic in colour, highlighting part numbers and gears.
+    RenderDay3 {
+        /// Path to the puzzle input, as for `run`.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+
+        /// Write an HTML page to this path instead of printing to the terminal.
+        #[arg(long)]
+        html: Option<PathBuf>,
+    },
+
+    /// Print the day 5 maps between two categories composed into a single map.
+    MapDay5 {
+        /// Path to the puzzle input, as for `run`.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+
+        #[arg(long, default_value = "seed")]
+        from: String,
+
+        #[arg(long, default_value = "location")]
+        to: String,
+
+        /// Print the map going the other way instead.
+        #[arg(long)]
+        invert: bool,
+
+        /// Reject empty, overflowing or overlapping entries instead of warning about them.
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Run every registered day and part against its default input, printing a timing table.
+    All {
+        /// Number of times to run each part, reporting the median and minimum timings.
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        repeat: u64,
+    },
+
+    /// Check each day's answers against the known-answer store.
+    Verify {
+        /// Only verify these days. Defaults to every registered day.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Vec<u8>,
+
+        /// Store answers for any parts that don't have a known answer yet.
+        #[arg(long)]
+        record: bool,
+
+        #[arg(long, default_value = DEFAULT_ANSWERS_PATH)]
+        answers: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum Day2Query {
+    /// List the games that are possible with a bag.
+    Possible {
+        /// Cubes in the bag, as `colour=count`. Defaults to the part 1 bag.
+        #[arg(short, long = "limit", value_parser = parse_limit)]
+        limits: Vec<(String, u32)>,
+    },
+
+    /// List the games that only become possible once the bag holds more of one colour.
+    Raise {
+        /// Cubes in the bag, as `colour=count`. Defaults to the part 1 bag.
+        #[arg(short, long = "limit", value_parser = parse_limit)]
+        limits: Vec<(String, u32)>,
+
+        #[arg(short, long)]
+        colour: String,
+
+        #[arg(short, long, default_value_t = 1)]
+        by: u32,
+    },
+
+    /// Find the bag with the fewest cubes that makes at least this many games possible.
+    Tightest {
+        #[arg(short, long)]
+        at_least: usize,
+    },
+
+    /// Summarise the fewest cubes of each colour each game needs.
+    Distribution,
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run { day, part, input } => match solve(day, part, input) {
+            Ok(answer) => {
+                println!("{}", answer);
+                ExitCode::SUCCESS
+            }
+            Err(message) => {
+                eprintln!("{}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Command::Submit { day, part, input } => {
+            let Some(solution) = solution::find(day) else {
+                eprintln!("No solution registered for day {}", day);
+                return ExitCode::FAILURE;
+            };
+            let buffer = match read_input(day, input) {
+                Ok(buffer) => buffer,
+                Err(message) => {
+                    eprintln!("{}", message);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let part = Part::from_number(part).expect("Part number should be validated by clap");
+
+            match SubmissionClient::from_env().submit_solution(solution, part, &buffer) {
+                Ok((answer, outcome)) => {
+                    println!("day {} part {}: {} ({})", day, part.number(), outcome, answer);
+                    if outcome == Outcome::Correct {
+                        ExitCode::SUCCESS
+                    } else {
+                        ExitCode::FAILURE
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Failed to submit: {}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::ExplainDay1 {
+            part,
+            input,
+            vocabulary,
+        } => match explain_day1(part, input, vocabulary) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("{}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Command::QueryDay2 { input, query } => match query_day2(input, query) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("{}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Command::RenderDay3 { input, html } => match render_day3(input, html) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("{}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Command::MapDay5 {
+            input,
+            from,
+            to,
+            invert,
+            strict,
+        } => match map_day5(input, &from, &to, invert, strict) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("{}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Command::All { repeat } => {
+            runner::run_all(repeat as usize);
+            ExitCode::SUCCESS
+        }
+        Command::Verify {
+            day,
+            record,
+            answers: path,
+        } => {
+            let mut answers = match Answers::load(&path) {
+                Ok(answers) => answers,
+                Err(err) => {
+                    eprintln!("Failed to load answers from '{}': {}", path.display(), err);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let all_passed = runner::verify(&day, &mut answers, record);
+
+            if record {
+                if let Err(err) = answers.save(&path) {
+                    eprintln!("Failed to save answers to '{}': {}", path.display(), err);
+                    return ExitCode::FAILURE;
+                }
+            }
+
+            if all_passed {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+/// Runs one part of one day, reading the input from `input` or the input cache.
+fn solve(day: u8, part: u8, input: Option<InputSource>) -> Result<String, String> {
+    let solution =
+        solution::find(day).ok_or_else(|| format!("No solution registered for day {}", day))?;
+    let part = Part::from_number(part).expect("Part number should be validated by clap");
+
+    let buffer = read_input(day, input)?;
+
+    solution
+        .run(&buffer, part)
+        .map(|run| run.answer)
+        .map_err(|err| format!("Error: {}", err))
+}
+
+fn read_input(day: u8, input: Option<InputSource>) -> Result<String, String> {
+    match input {
+        Some(source) => source
+            .read()
+            .map_err(|err| format!("Failed to read input '{}': {}", source, err)),
+        None => InputCache::from_env()
+            .get(day)
+            .map_err(|err| format!("Failed to get input for day {}: {}", day, err)),
+    }
+}
+
+/// Prints how each line of a day 1 calibration document is read, followed by the total.
+fn explain_day1(
+    part: u8,
+    input: Option<InputSource>,
+    vocabulary: Option<PathBuf>,
+) -> Result<(), String> {
+    let vocabulary = match (vocabulary, part) {
+        (Some(path), _) => Vocabulary::load(&path)
+            .map_err(|err| format!("Failed to load vocabulary '{}': {}", path.display(), err))?,
+        (None, 1) => Vocabulary::digits(),
+        (None, _) => Vocabulary::english(),
+    };
+    let buffer = read_input(1, input)?;
+    let explanations = explain(buffer.lines(), &vocabulary);
+
+    for explanation in &explanations {
+        println!("{}", explanation);
+    }
+
+    let values = explanations.iter().map(|explanation| explanation.value());
+    println!(
+        "\nTotal: {} ({} of {} lines had no digits)",
+        values.clone().flatten().sum::<u32>(),
+        values.filter(Option::is_none).count(),
+        explanations.len()
+    );
+    Ok(())
+}
+
+fn parse_limit(s: &str) -> Result<(String, u32), String> {
+    let (colour, count) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected colour=count, found '{}'", s))?;
+    let count = count
+        .parse()
+        .map_err(|_| format!("expected a number of cubes, found '{}'", count))?;
+    Ok((String::from(colour), count))
+}
+
+fn query_day2(input: Option<InputSource>, query: Day2Query) -> Result<(), String> {
+    let buffer = read_input(2, input)?;
+    let games = Day2::parse(&buffer).map_err(|err| format!("Error: {}", err))?;
+    let bag = |limits: Vec<(String, u32)>| -> Cubes {
+        if limits.is_empty() {
+            cubes(PART1_LIMITS)
+        } else {
+            limits.into_iter().collect()
+        }
+    };
+
+    match query {
+        Day2Query::Possible { limits } => {
+            println!("{}", query::possible_with(&games, &bag(limits)));
+        }
+        Day2Query::Raise { limits, colour, by } => {
+            println!("{}", query::newly_possible(&games, &bag(limits), &colour, by));
+        }
+        Day2Query::Tightest { at_least } => match query::tightest_bag(&games, at_least) {
+            Some(bag) => {
+                println!("{:?} ({} cubes)", bag.limits, bag.total());
+                println!("{}", bag.matches);
+            }
+            None => return Err(format!("There are only {} games", games.len())),
+        },
+        Day2Query::Distribution => {
+            for (colour, distribution) in query::distributions(&games) {
+                println!("{}: {}", colour, distribution);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn render_day3(input: Option<InputSource>, html: Option<PathBuf>) -> Result<(), String> {
+    let buffer = read_input(3, input)?;
+    let schematic = Day3::parse(&buffer).map_err(|err| format!("Error: {}", err))?;
+    let query = GearQuery::default();
+
+    match html {
+        Some(path) => fs::write(&path, render_html(&schematic, &query))
+            .map_err(|err| format!("Failed to write '{}': {}", path.display(), err)),
+        None => {
+            print!("{}", render_ansi(&schematic, &query));
+            Ok(())
+        }
+    }
+}
+
+fn map_day5(
+    input: Option<InputSource>,
+    from: &str,
+    to: &str,
+    invert: bool,
+    strict: bool,
+) -> Result<(), String> {
+    let buffer = read_input(5, input)?;
+    let validation = if strict {
+        Validation::Strict
+    } else {
+        Validation::Lenient
+    };
+
+    let (almanac, problems) =
+        Almanac::parse(&buffer, validation).map_err(|err| format!("Error: {}", err))?;
+    for problem in problems {
+        eprintln!("Warning: {}", problem);
+    }
+    let map = almanac
+        .map_between(from, to)
+        .map_err(|err| format!("Error: {}", err))?;
+
+    if invert {
+        println!("{}", map.invert());
+    } else {
+        println!("{}", map);
+    }
+    Ok(())
 }
@@ -0,0 +1,270 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    answers::Answers,
+    error::Error,
+    input::InputSource,
+    solution::{self, DynSolution, Part},
+};
+
+const LAST_DAY: u8 = 25;
+
+/// Median and minimum of a set of repeated timings.
+struct Timing {
+    median: Duration,
+    min: Duration,
+}
+
+enum Row {
+    Solved {
+        part: Part,
+        answer: String,
+        parse: Timing,
+        solve: Timing,
+    },
+    Skipped(&'static str),
+    Failed { part: Part, error: Error },
+}
+
+/// Runs every registered day and part `repeat` times, printing a table of answers and timings.
+pub fn run_all(repeat: usize) {
+    let start = Instant::now();
+    let show_min = repeat > 1;
+
+    if show_min {
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>21}  {:>21}",
+            "Day", "Part", "Answer", "Parse (median / min)", "Solve (median / min)"
+        );
+    } else {
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>10}  {:>10}",
+            "Day", "Part", "Answer", "Parse", "Solve"
+        );
+    }
+
+    for day in 1..=LAST_DAY {
+        for row in rows_for_day(day, repeat) {
+            match row {
+                Row::Solved {
+                    part,
+                    answer,
+                    parse,
+                    solve,
+                } => {
+                    if show_min {
+                        println!(
+                            "{:>3}  {:>4}  {:<20}  {:>10} / {:>8}  {:>10} / {:>8}",
+                            day,
+                            part.number(),
+                            answer,
+                            format_duration(parse.median),
+                            format_duration(parse.min),
+                            format_duration(solve.median),
+                            format_duration(solve.min),
+                        );
+                    } else {
+                        println!(
+                            "{:>3}  {:>4}  {:<20}  {:>10}  {:>10}",
+                            day,
+                            part.number(),
+                            answer,
+                            format_duration(parse.median),
+                            format_duration(solve.median),
+                        );
+                    }
+                }
+                Row::Skipped(reason) => println!("{:>3}  {:>4}  {}", day, "-", reason),
+                Row::Failed { part, error } => {
+                    println!("{:>3}  {:>4}  error: {}", day, part.number(), error)
+                }
+            }
+        }
+    }
+
+    println!("\nTotal wall time: {}", format_duration(start.elapsed()));
+}
+
+fn rows_for_day(day: u8, repeat: usize) -> Vec<Row> {
+    let Some(solution) = solution::find(day) else {
+        return vec![Row::Skipped("missing")];
+    };
+    if !solution.finished() {
+        return vec![Row::Skipped("unfinished")];
+    }
+    let Some(input) = load_input(day) else {
+        return vec![Row::Skipped("no input")];
+    };
+
+    Part::BOTH
+        .into_iter()
+        .map(|part| {
+            let mut answer = String::new();
+            let mut parse_times = vec![];
+            let mut solve_times = vec![];
+
+            for _ in 0..repeat {
+                let run = match solution.run(&input, part) {
+                    Ok(run) => run,
+                    Err(error) => return Row::Failed { part, error },
+                };
+                parse_times.push(run.parse_time);
+                solve_times.push(run.solve_time);
+                answer = run.answer;
+            }
+
+            Row::Solved {
+                part,
+                answer,
+                parse: Timing::from(parse_times),
+                solve: Timing::from(solve_times),
+            }
+        })
+        .collect()
+}
+
+/// Outcome of checking a freshly computed answer against the answer store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+/// Runs the given days (or every registered day) against their default inputs and compares each
+/// answer with the store. When `record` is set, answers for parts without a stored answer are
+/// added to the store. Returns whether every known answer matched; asking for a day that has no
+/// solution counts as a failure.
+pub fn verify(days: &[u8], answers: &mut Answers, record: bool) -> bool {
+    let days = if days.is_empty() {
+        solution::REGISTRY.iter().map(|solution| solution.day()).collect()
+    } else {
+        days.to_vec()
+    };
+    let mut all_passed = true;
+
+    for day in days {
+        let Some(solution) = solution::find(day) else {
+            all_passed = false;
+            println!("day {:>2}: ERROR (no solution registered)", day);
+            continue;
+        };
+        if !solution.finished() {
+            println!("day {:>2}: SKIPPED (unfinished)", day);
+            continue;
+        }
+        let Some(input) = load_input(day) else {
+            println!("day {:>2}: SKIPPED (no input)", day);
+            continue;
+        };
+
+        let verdicts = verify_day(solution, &input, answers, record);
+        all_passed &= verdicts
+            .iter()
+            .all(|verdict| matches!(verdict, Some(Verdict::Pass | Verdict::Unknown)));
+    }
+    all_passed
+}
+
+/// Runs both parts of one day, printing how each compares with the store. Parts that fail to run
+/// have no verdict.
+fn verify_day(
+    solution: &dyn DynSolution,
+    input: &str,
+    answers: &mut Answers,
+    record: bool,
+) -> Vec<Option<Verdict>> {
+    let day = solution.day();
+
+    Part::BOTH
+        .into_iter()
+        .map(|part| {
+            let answer = match solution.run(input, part) {
+                Ok(run) => run.answer,
+                Err(err) => {
+                    println!("day {:>2} part {}: ERROR ({})", day, part.number(), err);
+                    return None;
+                }
+            };
+            let verdict = match answers.get(day, part) {
+                Some(expected) if expected == answer => Verdict::Pass,
+                Some(_) => Verdict::Fail,
+                None => Verdict::Unknown,
+            };
+
+            match verdict {
+                Verdict::Pass => println!("day {:>2} part {}: PASS ({})", day, part.number(), answer),
+                Verdict::Fail => println!(
+                    "day {:>2} part {}: FAIL (expected {}, got {})",
+                    day,
+                    part.number(),
+                    answers.get(day, part).unwrap_or_default(),
+                    answer
+                ),
+                Verdict::Unknown if record => {
+                    println!("day {:>2} part {}: RECORDED ({})", day, part.number(), answer);
+                    answers.record(day, part, answer);
+                }
+                Verdict::Unknown => {
+                    println!("day {:>2} part {}: UNKNOWN ({})", day, part.number(), answer)
+                }
+            }
+            Some(verdict)
+        })
+        .collect()
+}
+
+fn load_input(day: u8) -> Option<String> {
+    InputSource::default_for(day).read().ok()
+}
+
+impl Timing {
+    pub fn from(mut times: Vec<Duration>) -> Self {
+        times.sort();
+        Self {
+            median: times[times.len() / 2],
+            min: times[0],
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY9_EXAMPLE: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+
+    #[test]
+    fn compares_answers_with_the_store() {
+        let day9 = solution::find(9).unwrap();
+        let mut answers = Answers::default();
+        answers.record(9, Part::One, String::from("114"));
+        answers.record(9, Part::Two, String::from("3"));
+
+        let verdicts = verify_day(day9, DAY9_EXAMPLE, &mut answers, false);
+        assert_eq!(verdicts, [Some(Verdict::Pass), Some(Verdict::Fail)]);
+    }
+
+    #[test]
+    fn records_unknown_answers_only_when_asked() {
+        let day9 = solution::find(9).unwrap();
+        let mut answers = Answers::default();
+
+        let verdicts = verify_day(day9, DAY9_EXAMPLE, &mut answers, false);
+        assert_eq!(verdicts, [Some(Verdict::Unknown), Some(Verdict::Unknown)]);
+        assert_eq!(answers.get(9, Part::One), None);
+
+        verify_day(day9, DAY9_EXAMPLE, &mut answers, true);
+        assert_eq!(answers.get(9, Part::One), Some("114"));
+        assert_eq!(answers.get(9, Part::Two), Some("2"));
+    }
+
+    #[test]
+    fn fails_on_days_without_a_solution() {
+        assert!(!verify(&[10], &mut Answers::default(), false));
+    }
+}
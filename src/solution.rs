@@ -0,0 +1,115 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::Result,
+    day1::Day1, day11::Day11, day12::Day12, day13::Day13, day17::Day17, day19::Day19, day2::Day2,
+    day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8, day9::Day9,
+};
+
+/// Every implemented day, in order.
+pub static REGISTRY: &[&dyn DynSolution] = &[
+    &Day1, &Day2, &Day3, &Day4, &Day5, &Day6, &Day7, &Day8, &Day9, &Day11, &Day12, &Day13, &Day17,
+    &Day19,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// A single day's puzzle: a parse step shared by both parts, followed by the two parts themselves.
+pub trait Solution {
+    const DAY: u8;
+
+    /// Whether both parts produce real answers, as opposed to placeholders.
+    const FINISHED: bool = true;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<impl Display>;
+
+    fn part2(input: &Self::Input) -> Result<impl Display>;
+}
+
+/// Object-safe view of a `Solution`, so that days with different input types can share a registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    fn finished(&self) -> bool;
+
+    fn run(&self, input: &str, part: Part) -> Result<Run>;
+}
+
+/// The answer from a single run, along with how long each stage took.
+pub struct Run {
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn finished(&self) -> bool {
+        S::FINISHED
+    }
+
+    fn run(&self, input: &str, part: Part) -> Result<Run> {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse_time = start.elapsed();
+
+        let start = Instant::now();
+        let answer = match part {
+            Part::One => S::part1(&parsed)?.to_string(),
+            Part::Two => S::part2(&parsed)?.to_string(),
+        };
+        let solve_time = start.elapsed();
+
+        Ok(Run {
+            answer,
+            parse_time,
+            solve_time,
+        })
+    }
+}
+
+/// Placeholder answer for parts that haven't been solved yet.
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unsolved")
+    }
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(n: u8) -> Option<Self> {
+        match n {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+    REGISTRY.iter().find(|solution| solution.day() == day).copied()
+}
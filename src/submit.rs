@@ -0,0 +1,471 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    env, fmt, fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::{
+    fetch::{unix_time, BASE_URL, SESSION_ENV_VAR, USER_AGENT, YEAR},
+    solution::{DynSolution, Part},
+    util::INPUT_DIR,
+};
+
+pub const DEFAULT_SUBMISSION_LOG: &str = "submissions.log";
+
+static WAIT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").expect("Invalid regex")
+});
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which direction.
+    Wrong,
+    RateLimited { wait: Duration },
+    AlreadySolved,
+    /// A response that didn't look like any of the above, kept as-is.
+    Unrecognised(String),
+}
+
+impl Outcome {
+    /// Reads the outcome out of the HTML page returned after submitting an answer.
+    pub fn from_response(body: &str) -> Self {
+        if body.contains("That's the right answer") {
+            Outcome::Correct
+        } else if body.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if body.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if body.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if body.contains("You gave an answer too recently") {
+            let wait = WAIT_REGEX.captures(body).map_or(0, |captures| {
+                let minutes = captures.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                let seconds = captures[2].parse().unwrap_or(0);
+                minutes * 60 + seconds
+            });
+            Outcome::RateLimited {
+                wait: Duration::from_secs(wait),
+            }
+        } else if body.contains("Did you already complete it") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unrecognised(body.trim().to_string())
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+
+    /// The outcome as it's written to the submission log.
+    fn key(&self) -> String {
+        match self {
+            Outcome::Correct => String::from("correct"),
+            Outcome::TooHigh => String::from("too-high"),
+            Outcome::TooLow => String::from("too-low"),
+            Outcome::Wrong => String::from("wrong"),
+            Outcome::RateLimited { wait } => format!("rate-limited:{}", wait.as_secs()),
+            Outcome::AlreadySolved => String::from("already-solved"),
+            Outcome::Unrecognised(_) => String::from("unrecognised"),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "already-solved" => Outcome::AlreadySolved,
+            "unrecognised" => Outcome::Unrecognised(String::new()),
+            _ => Outcome::RateLimited {
+                wait: Duration::from_secs(key.strip_prefix("rate-limited:")?.parse().ok()?),
+            },
+        })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited { wait } => {
+                write!(f, "rate limited, try again in {}s", wait.as_secs())
+            }
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::Unrecognised(body) => write!(f, "unrecognised response: {}", body),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Io(io::Error),
+    MissingSession,
+    Http(String),
+    /// The answer was refused locally, without contacting the server.
+    Refused(String),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Io(err) => write!(f, "{}", err),
+            SubmitError::MissingSession => {
+                write!(f, "set {} to your adventofcode.com session cookie", SESSION_ENV_VAR)
+            }
+            SubmitError::Http(reason) => write!(f, "request failed: {}", reason),
+            SubmitError::Refused(reason) => write!(f, "not submitting: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<io::Error> for SubmitError {
+    fn from(err: io::Error) -> Self {
+        SubmitError::Io(err)
+    }
+}
+
+/// Something that can hand an answer to the server and report back what it said.
+pub trait Submitter {
+    fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Outcome, SubmitError>;
+}
+
+/// Posts answers over HTTP, authenticating with the session cookie.
+pub struct HttpSubmitter {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl HttpSubmitter {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(BASE_URL, env::var(SESSION_ENV_VAR).ok())
+    }
+}
+
+impl Submitter for HttpSubmitter {
+    fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Outcome, SubmitError> {
+        let session = self.session.as_ref().ok_or(SubmitError::MissingSession)?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let level = part.number().to_string();
+
+        let body = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", session))
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|err| SubmitError::Http(err.to_string()))?
+            .into_string()?;
+
+        Ok(Outcome::from_response(&body))
+    }
+}
+
+/// One line of the submission log: `[day] [part] [unix seconds] [outcome] [answer]`.
+#[derive(Debug, Clone)]
+struct Attempt {
+    day: u8,
+    part: u8,
+    time: Duration,
+    outcome: Outcome,
+    answer: String,
+}
+
+impl Attempt {
+    fn from(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, ' ');
+        Some(Self {
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            time: Duration::from_secs(fields.next()?.parse().ok()?),
+            outcome: Outcome::from_key(fields.next()?)?,
+            answer: fields.next()?.to_string(),
+        })
+    }
+}
+
+/// Submits answers, keeping a log of every attempt so that answers which are certain to be wrong
+/// are never sent: ones already rejected, ones on the wrong side of a too-high/too-low hint, and
+/// anything sent while the server has asked us to wait.
+pub struct SubmissionClient<S: Submitter> {
+    submitter: S,
+    log_path: PathBuf,
+}
+
+impl SubmissionClient<HttpSubmitter> {
+    /// Logs to `input/submissions.log`, submitting to adventofcode.com.
+    pub fn from_env() -> Self {
+        Self::new(
+            PathBuf::from(INPUT_DIR).join(DEFAULT_SUBMISSION_LOG),
+            HttpSubmitter::from_env(),
+        )
+    }
+}
+
+impl<S: Submitter> SubmissionClient<S> {
+    pub fn new(log_path: impl Into<PathBuf>, submitter: S) -> Self {
+        Self {
+            submitter,
+            log_path: log_path.into(),
+        }
+    }
+
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Outcome, SubmitError> {
+        let now = unix_time();
+        let attempts = self
+            .read_log()?
+            .into_iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part.number())
+            .collect::<Vec<_>>();
+
+        if let Some(reason) = refusal(&attempts, answer, now) {
+            return Err(SubmitError::Refused(reason));
+        }
+
+        let outcome = self.submitter.submit(day, part, answer)?;
+        self.append_to_log(&Attempt {
+            day,
+            part: part.number(),
+            time: now,
+            outcome: outcome.clone(),
+            answer: answer.to_string(),
+        })?;
+        Ok(outcome)
+    }
+
+    /// Solves one part of a day and submits the answer. Unfinished days only produce
+    /// placeholder answers, so they're refused without solving or contacting the server.
+    pub fn submit_solution(
+        &self,
+        solution: &dyn DynSolution,
+        part: Part,
+        input: &str,
+    ) -> Result<(String, Outcome), SubmitError> {
+        if !solution.finished() {
+            return Err(SubmitError::Refused(format!("day {} isn't finished", solution.day())));
+        }
+
+        let answer = solution
+            .run(input, part)
+            .map_err(|err| SubmitError::Refused(err.to_string()))?
+            .answer;
+        let outcome = self.submit(solution.day(), part, &answer)?;
+        Ok((answer, outcome))
+    }
+
+    fn read_log(&self) -> io::Result<Vec<Attempt>> {
+        match fs::read_to_string(&self.log_path) {
+            Ok(log) => Ok(log.lines().filter_map(Attempt::from).collect()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn append_to_log(&self, attempt: &Attempt) -> io::Result<()> {
+        if let Some(dir) = self.log_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)?;
+
+        writeln!(
+            log,
+            "{} {} {} {} {}",
+            attempt.day,
+            attempt.part,
+            attempt.time.as_secs(),
+            attempt.outcome.key(),
+            attempt.answer
+        )
+    }
+}
+
+/// Why `answer` shouldn't be submitted, given the earlier attempts at the same part.
+fn refusal(attempts: &[Attempt], answer: &str, now: Duration) -> Option<String> {
+    if let Some(correct) = attempts.iter().find(|a| a.outcome == Outcome::Correct) {
+        return Some(format!("already solved with '{}'", correct.answer));
+    }
+
+    if attempts.iter().any(|a| a.answer == answer && a.outcome.is_wrong()) {
+        return Some(format!("'{}' was already rejected", answer));
+    }
+
+    let wait_until = attempts
+        .iter()
+        .filter_map(|a| match a.outcome {
+            Outcome::RateLimited { wait } => Some(a.time + wait),
+            _ => None,
+        })
+        .max();
+    if let Some(wait_until) = wait_until.filter(|until| *until > now) {
+        return Some(format!("rate limited for another {}s", (wait_until - now).as_secs()));
+    }
+
+    let value = answer.parse::<i128>().ok()?;
+    let bound = |outcome: Outcome| {
+        attempts
+            .iter()
+            .filter(move |a| a.outcome == outcome)
+            .filter_map(|a| a.answer.parse::<i128>().ok())
+    };
+
+    if let Some(too_high) = bound(Outcome::TooHigh).min().filter(|high| value >= *high) {
+        return Some(format!("{} was already too high", too_high));
+    }
+    if let Some(too_low) = bound(Outcome::TooLow).max().filter(|low| value <= *low) {
+        return Some(format!("{} was already too low", too_low));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    fn log_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-submit-{}-{}.log", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    /// Pretends the answer to every part is 42.
+    fn serve_answers() -> MockServer {
+        MockServer::start(|request| {
+            let answer = request
+                .split("answer=")
+                .nth(1)
+                .and_then(|answer| answer.parse::<i64>().ok());
+
+            let article = match answer {
+                Some(42) => "That's the right answer! You are one gold star closer.",
+                Some(n) if n > 42 => "That's not the right answer; your answer is too high.",
+                Some(_) => "That's not the right answer; your answer is too low.",
+                None => "That's not the right answer.",
+            };
+            (200, format!("<main><article><p>{}</p></article></main>", article))
+        })
+    }
+
+    fn client(server: &MockServer, name: &str) -> SubmissionClient<HttpSubmitter> {
+        SubmissionClient::new(
+            log_path(name),
+            HttpSubmitter::new(&server.url, Some(String::from("secret"))),
+        )
+    }
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(Outcome::from_response("That's the right answer!"), Outcome::Correct);
+        assert_eq!(
+            Outcome::from_response("That's not the right answer; your answer is too low."),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::from_response(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 4m 31s left to wait."
+            ),
+            Outcome::RateLimited {
+                wait: Duration::from_secs(271)
+            }
+        );
+        assert_eq!(
+            Outcome::from_response("You don't seem to be solving the right level.  Did you already complete it?"),
+            Outcome::AlreadySolved
+        );
+    }
+
+    #[test]
+    fn posts_answer_and_logs_attempt() {
+        let server = serve_answers();
+        let client = client(&server, "post");
+
+        assert_eq!(client.submit(6, Part::Two, "42").unwrap(), Outcome::Correct);
+
+        let request = &server.requests()[0];
+        assert!(request.starts_with("POST /2023/day/6/answer "));
+        assert!(request.contains("Cookie: session=secret"));
+        assert!(request.ends_with("level=2&answer=42"));
+        assert_eq!(client.read_log().unwrap()[0].outcome, Outcome::Correct);
+    }
+
+    #[test]
+    fn refuses_to_submit_unfinished_days() {
+        let server = serve_answers();
+        let client = client(&server, "unfinished");
+        let day19 = crate::solution::find(19).unwrap();
+
+        assert!(matches!(
+            client.submit_solution(day19, Part::One, ""),
+            Err(SubmitError::Refused(reason)) if reason == "day 19 isn't finished"
+        ));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn refuses_answers_known_to_be_wrong() {
+        let server = serve_answers();
+        let client = client(&server, "known-wrong");
+
+        assert_eq!(client.submit(1, Part::One, "abc").unwrap(), Outcome::Wrong);
+        assert!(matches!(client.submit(1, Part::One, "abc"), Err(SubmitError::Refused(_))));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn refuses_answers_outside_learned_bounds() {
+        let server = serve_answers();
+        let client = client(&server, "bounds");
+
+        assert_eq!(client.submit(1, Part::One, "100").unwrap(), Outcome::TooHigh);
+        assert_eq!(client.submit(1, Part::One, "10").unwrap(), Outcome::TooLow);
+
+        for answer in ["100", "150", "10", "3"] {
+            assert!(matches!(client.submit(1, Part::One, answer), Err(SubmitError::Refused(_))));
+        }
+        assert_eq!(client.submit(1, Part::One, "42").unwrap(), Outcome::Correct);
+        assert!(matches!(client.submit(1, Part::One, "43"), Err(SubmitError::Refused(_))));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn waits_out_rate_limit() {
+        let server = MockServer::start(|_| {
+            (200, String::from("You gave an answer too recently. You have 30s left to wait."))
+        });
+        let client = client(&server, "rate-limit");
+
+        assert_eq!(
+            client.submit(2, Part::One, "8").unwrap(),
+            Outcome::RateLimited {
+                wait: Duration::from_secs(30)
+            }
+        );
+        assert!(matches!(client.submit(2, Part::One, "8"), Err(SubmitError::Refused(_))));
+        assert_eq!(server.requests().len(), 1);
+    }
+}
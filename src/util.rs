@@ -1,33 +1,21 @@
 use std::{
-	env,
-	fs::File,
-	io::{self, BufRead, BufReader, Read}, str::FromStr,
+	path::{Path, PathBuf},
+	str::FromStr,
 };
 use regex::Regex;
 
-pub fn read_input_lines() -> impl Iterator<Item = String> {
-	BufReader::new(get_input_reader())
-		.lines()
-		.flat_map(Result::ok)
-}
+use crate::error::{unexpected, Unexpected};
+
+pub const INPUT_DIR: &str = "input";
 
-pub fn get_input_reader() -> BufReader<Box<dyn Read>> {
-	let reader: Box<dyn Read> = if let Some(filename) = filename_from_args() {
-		Box::new(File::open(&filename).expect(&format!("No such file {}!", filename)))
-	} else {
-		Box::new(io::stdin())
-	};
-	BufReader::new(reader)
+pub fn default_input_path(day: u8) -> PathBuf {
+	Path::new(INPUT_DIR).join(format!("day{}.txt", day))
 }
 
-pub fn lines_as<T: FromStr>(s: &str) -> Vec<T> {
-	s.lines().flat_map(|line| line.parse()).collect()
+pub fn parse_num<T: FromStr>(s: &str) -> Result<T, Unexpected<'_>> {
+	s.parse().map_err(|_| unexpected(s, "a number"))
 }
 
 pub fn regex_or_panic(s: &str) -> Regex {
 	Regex::new(s).expect("Invalid regular expression")
 }
-
-fn filename_from_args() -> Option<String> {
-	env::args().skip(1).collect::<Vec<_>>().first().cloned()
-}
@@ -0,0 +1,42 @@
+use advent_of_code_2023::{
+    day17::grid::Grid,
+    day19::workflow::Workflow,
+    day5::SeedMap,
+    solution::{self, Part},
+};
+
+#[test]
+fn registry_runs_solutions_from_outside_the_crate() {
+    let day9 = solution::find(9).expect("day 9 should be registered");
+    let run = day9.run("0 3 6 9 12 15\n", Part::One).unwrap();
+    assert_eq!(run.answer, "18");
+}
+
+#[test]
+fn seed_map_looks_up_mapped_and_unmapped_numbers() {
+    let map = SeedMap::from("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
+    assert_eq!(map.source, "seed");
+    assert_eq!(map.dest, "soil");
+    assert_eq!(map.lookup(79), 81);
+    assert_eq!(map.lookup(99), 51);
+    assert_eq!(map.lookup(10), 10);
+}
+
+#[test]
+fn grid_neighbours_stay_within_bounds() {
+    let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+    let neighbours = |i| {
+        let mut found = grid.neighbours(i).into_iter().map(|(_, c)| *c).collect::<Vec<_>>();
+        found.sort();
+        found
+    };
+    assert_eq!(neighbours(0), ['b', 'd']);
+    assert_eq!(neighbours(4), ['b', 'd', 'f', 'h']);
+    assert_eq!(neighbours(8), ['f', 'h']);
+}
+
+#[test]
+fn workflow_parses_name() {
+    let workflow = Workflow::from("px{a<2006:qkq,m>2090:A,rfg}").unwrap();
+    assert_eq!(workflow.name, "px");
+}